@@ -18,7 +18,7 @@ async fn main() -> Result<()> {
         }
         Err(e) => {
             error!("❌ Failed to load configuration: {}", e);
-            return Err(e);
+            return Err(e.into());
         }
     };
     
@@ -30,7 +30,7 @@ async fn main() -> Result<()> {
         }
         Err(e) => {
             error!("❌ Failed to initialize NetSuite client: {}", e);
-            return Err(e);
+            return Err(e.into());
         }
     };
     
@@ -50,8 +50,9 @@ async fn main() -> Result<()> {
         Ok(customers) => {
             info!("✅ Successfully fetched {} customers", customers.len());
             for customer in customers.iter().take(3) {
-                info!("   - {} ({})", customer.entityid, customer.companyname.as_deref().unwrap_or("No company name"));
+                info!("   - {} ({})", customer.entity_id, customer.company_name.as_deref().unwrap_or("No company name"));
             }
+            process_customers(customers);
         }
         Err(e) => {
             warn!("⚠️  Failed to fetch customers (expected without real credentials): {}", e);
@@ -69,7 +70,7 @@ fn process_customers(customers: Vec<Customer>) {
     
     let active_customers: Vec<&Customer> = customers
         .iter()
-        .filter(|c| c.companyname.is_some())
+        .filter(|c| c.company_name.is_some())
         .collect();
     
     info!("Found {} customers with company names", active_customers.len());
@@ -78,8 +79,8 @@ fn process_customers(customers: Vec<Customer>) {
     let mut by_name_length: std::collections::HashMap<usize, Vec<&Customer>> = std::collections::HashMap::new();
     
     for customer in active_customers {
-        let name_length = customer.companyname.as_ref().unwrap().len();
-        by_name_length.entry(name_length).or_insert_with(Vec::new).push(customer);
+        let name_length = customer.company_name.as_ref().unwrap().len();
+        by_name_length.entry(name_length).or_default().push(customer);
     }
     
     for (length, customers) in by_name_length {
@@ -1,5 +1,6 @@
 use anyhow::Result;
-use netsuite_client::{NetSuiteClient, AppConfig};
+use chrono::NaiveDate;
+use netsuite_client::{NetSuiteClient, AppConfig, Filter};
 use tracing::{info, warn, error};
 
 #[tokio::main]
@@ -17,7 +18,7 @@ async fn main() -> Result<()> {
         }
         Err(e) => {
             error!("❌ Failed to load configuration: {}", e);
-            return Err(e);
+            return Err(e.into());
         }
     };
     
@@ -29,24 +30,25 @@ async fn main() -> Result<()> {
         }
         Err(e) => {
             error!("❌ Failed to initialize NetSuite client: {}", e);
-            return Err(e);
+            return Err(e.into());
         }
     };
     
     // Example 1: Fetch vendor payments for May-Aug 2024
     info!("Example 1: Fetching vendor payments for May-Aug 2024...");
-    info!("This implements: SELECT * FROM Transaction WHERE Type='VendPymt' AND (createddate like '%05/%/2024%' OR createddate like '%06/%/2024%' OR createddate like '%07/%/2024%' OR createddate like '%08/%/2024%')");
+    info!("Each payment is fetched with its apply sublist, so the bills it paid are known");
 
     match client.get_vendor_payments_2024(Some(10)).await {
-        Ok(transactions) => {
-            info!("✅ Successfully fetched {} vendor payments", transactions.len());
-            for (i, transaction) in transactions.iter().enumerate().take(3) {
-                info!("   {}. {} - ${:.2} - {} ({})", 
+        Ok(payments) => {
+            info!("✅ Successfully fetched {} vendor payments", payments.len());
+            for (i, payment) in payments.iter().enumerate().take(3) {
+                info!("   {}. {} - ${:.2} - {} ({}) - {} bills", 
                     i + 1, 
-                    transaction.id,
-                    transaction.amount.unwrap_or(0.0),
-                    transaction.memo.as_deref().unwrap_or("No memo"),
-                    transaction.createddate.as_deref().unwrap_or("No date")
+                    payment.id.as_deref().unwrap_or("-"),
+                    payment.total.unwrap_or(0.0),
+                    payment.memo.as_deref().unwrap_or("No memo"),
+                    payment.created_date.as_deref().unwrap_or("No date"),
+                    payment.settlements().len()
                 );
             }
         }
@@ -58,12 +60,11 @@ async fn main() -> Result<()> {
     
     // Example 2: Custom filters
     info!("Example 2: Using custom filters...");
-    let custom_filters = vec![
-        "type IS VendPymt".to_string(),
-        "createddate BETWEEN '2024-05-01' AND '2024-08-31'".to_string(),
-    ];
+    let custom_filter = Filter::field("type").is("VendPymt").and(
+        Filter::field("createddate").between(date(2024, 5, 1), date(2024, 8, 31))
+    );
     
-    match client.get_transactions_with_filters(&custom_filters, Some(10)).await {
+    match client.get_transactions_with_filters(Some(&custom_filter), Some(10)).await {
         Ok(transactions) => {
             info!("✅ Successfully fetched {} transactions with custom filters", transactions.len());
             for (i, transaction) in transactions.iter().enumerate().take(3) {
@@ -72,7 +73,7 @@ async fn main() -> Result<()> {
                     transaction.id,
                     transaction.amount.unwrap_or(0.0),
                     transaction.transaction_type.as_deref().unwrap_or("Unknown"),
-                    transaction.createddate.as_deref().unwrap_or("No date")
+                    transaction.created_date.as_deref().unwrap_or("No date")
                 );
             }
         }
@@ -84,12 +85,11 @@ async fn main() -> Result<()> {
     
     // Example 3: Different transaction types
     info!("Example 3: Fetching different transaction types...");
-    let sales_filters = vec![
-        "type IS SalesOrd".to_string(),
-        "createddate BETWEEN '2024-01-01' AND '2024-12-31'".to_string(),
-    ];
+    let sales_filter = Filter::field("type").is("SalesOrd").and(
+        Filter::field("createddate").between(date(2024, 1, 1), date(2024, 12, 31))
+    );
     
-    match client.get_transactions_with_filters(&sales_filters, Some(5)).await {
+    match client.get_transactions_with_filters(Some(&sales_filter), Some(5)).await {
         Ok(transactions) => {
             info!("✅ Successfully fetched {} sales orders", transactions.len());
             for (i, transaction) in transactions.iter().enumerate().take(3) {
@@ -110,3 +110,7 @@ async fn main() -> Result<()> {
     info!("=== Transaction Query Examples Completed ===");
     Ok(())
 }
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).expect("valid date")
+}
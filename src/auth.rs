@@ -0,0 +1,141 @@
+pub mod authorization_code;
+pub mod m2m;
+pub mod tba;
+
+pub use authorization_code::{AuthorizationCodeProvider, StoredToken, TokenCache};
+pub use m2m::M2mTokenProvider;
+pub use tba::TbaSigner;
+
+use async_trait::async_trait;
+use reqwest::{Client, Method, Url, header::{HeaderMap, HeaderValue, AUTHORIZATION}};
+
+use crate::config::{AuthMethod, NetSuiteConfig};
+use crate::error::Result;
+
+/// Outgoing request as seen by an `Authenticator`
+pub struct AuthRequest<'a> {
+    pub method: &'a Method,
+    pub url: &'a Url,
+    pub body: Option<&'a [u8]>,
+}
+
+/// Produces the authentication headers for each outgoing request.
+///
+/// Implement this to plug a custom scheme (e.g. a vault-backed signer) into `NetSuiteClient::with_authenticator`.
+#[async_trait]
+pub trait Authenticator: Send + Sync {
+    /// Headers to add to the request, typically just `Authorization`
+    async fn authenticate(&self, request: &AuthRequest<'_>) -> Result<HeaderMap>;
+
+    /// Called after NetSuite answered 401; return `true` if retrying with fresh headers may succeed
+    async fn handle_unauthorized(&self) -> Result<bool> {
+        Ok(false)
+    }
+}
+
+/// Source of OAuth 2.0 access tokens for `BearerAuthenticator`
+#[async_trait]
+pub trait TokenSource: Send + Sync {
+    /// A currently valid access token
+    async fn access_token(&self) -> Result<String>;
+
+    /// Discard or renew the current token after it was rejected
+    async fn renew(&self) -> Result<()>;
+}
+
+#[async_trait]
+impl TokenSource for M2mTokenProvider {
+    async fn access_token(&self) -> Result<String> {
+        M2mTokenProvider::access_token(self).await
+    }
+
+    async fn renew(&self) -> Result<()> {
+        self.invalidate().await;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl TokenSource for AuthorizationCodeProvider {
+    async fn access_token(&self) -> Result<String> {
+        AuthorizationCodeProvider::access_token(self).await
+    }
+
+    async fn renew(&self) -> Result<()> {
+        self.refresh().await
+    }
+}
+
+/// Token-Based Authentication: a fresh OAuth 1.0a signature per request
+#[async_trait]
+impl Authenticator for TbaSigner {
+    async fn authenticate(&self, request: &AuthRequest<'_>) -> Result<HeaderMap> {
+        let mut headers = HeaderMap::new();
+        let auth_value = self.authorization_header(request.method, request.url)?;
+        headers.insert(AUTHORIZATION, HeaderValue::from_str(&auth_value)?);
+        Ok(headers)
+    }
+}
+
+/// OAuth 2.0 bearer tokens, renewed through the token source when NetSuite rejects them
+pub struct BearerAuthenticator<S> {
+    source: S,
+}
+
+impl<S: TokenSource> BearerAuthenticator<S> {
+    pub fn new(source: S) -> Self {
+        Self { source }
+    }
+}
+
+#[async_trait]
+impl<S: TokenSource> Authenticator for BearerAuthenticator<S> {
+    async fn authenticate(&self, _request: &AuthRequest<'_>) -> Result<HeaderMap> {
+        let mut headers = HeaderMap::new();
+        let auth_value = format!("Bearer {}", self.source.access_token().await?);
+        headers.insert(AUTHORIZATION, HeaderValue::from_str(&auth_value)?);
+        Ok(headers)
+    }
+
+    async fn handle_unauthorized(&self) -> Result<bool> {
+        self.source.renew().await?;
+        Ok(true)
+    }
+}
+
+/// Sends a fixed set of headers with every request; `StaticHeaders::none()` sends nothing.
+/// Useful for tests and for proxies that add credentials themselves.
+#[derive(Debug, Clone, Default)]
+pub struct StaticHeaders {
+    headers: HeaderMap,
+}
+
+impl StaticHeaders {
+    pub fn new(headers: HeaderMap) -> Self {
+        Self { headers }
+    }
+
+    pub fn none() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl Authenticator for StaticHeaders {
+    async fn authenticate(&self, _request: &AuthRequest<'_>) -> Result<HeaderMap> {
+        Ok(self.headers.clone())
+    }
+}
+
+/// Build the authenticator selected by `NetSuiteConfig::auth_method`
+pub fn from_config(config: &NetSuiteConfig, http_client: Client) -> Result<Box<dyn Authenticator>> {
+    Ok(match config.auth_method {
+        AuthMethod::Tba => Box::new(TbaSigner::new(config)),
+        AuthMethod::M2m => Box::new(BearerAuthenticator::new(
+            M2mTokenProvider::new(config, config.token_url(), http_client)?
+        )),
+        AuthMethod::AuthorizationCode => Box::new(BearerAuthenticator::new(
+            AuthorizationCodeProvider::new(config)?
+        )),
+    })
+}
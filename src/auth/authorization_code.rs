@@ -0,0 +1,364 @@
+use oauth2::{
+    basic::{BasicClient, BasicTokenResponse},
+    reqwest::async_http_client,
+    AuthUrl, AuthorizationCode, ClientId, ClientSecret, CsrfToken, PkceCodeChallenge, RedirectUrl,
+    RefreshToken, Scope, TokenResponse, TokenUrl,
+};
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio::sync::Mutex;
+use tracing::{debug, info};
+
+use crate::config::NetSuiteConfig;
+use crate::error::{NetSuiteError, Result};
+
+/// Refresh this long before the stored token lapses
+const REFRESH_MARGIN: Duration = Duration::from_secs(60);
+
+/// Access and refresh tokens as persisted in the token cache file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredToken {
+    pub access_token: String,
+    pub refresh_token: Option<String>,
+    /// Expiry as seconds since the Unix epoch
+    pub expires_at: u64,
+}
+
+impl StoredToken {
+    fn from_response(response: &BasicTokenResponse, previous_refresh_token: Option<String>) -> Self {
+        let expires_in = response.expires_in().unwrap_or(Duration::from_secs(3600));
+        Self {
+            access_token: response.access_token().secret().clone(),
+            // NetSuite does not always rotate the refresh token, so keep the old one
+            refresh_token: response
+                .refresh_token()
+                .map(|t| t.secret().clone())
+                .or(previous_refresh_token),
+            expires_at: unix_now() + expires_in.as_secs(),
+        }
+    }
+
+    fn is_expiring(&self) -> bool {
+        unix_now() + REFRESH_MARGIN.as_secs() >= self.expires_at
+    }
+}
+
+/// Token cache file, readable and writable by the owner only
+pub struct TokenCache {
+    path: PathBuf,
+}
+
+impl TokenCache {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn load(&self) -> Result<Option<StoredToken>> {
+        match std::fs::read(&self.path) {
+            Ok(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(NetSuiteError::Authentication(format!("Failed to read token cache {}: {}", self.path.display(), e))),
+        }
+    }
+
+    /// Write the tokens to a temporary file next to the cache and rename it into place,
+    /// so a crash never leaves a truncated refresh token behind
+    pub fn save(&self, token: &StoredToken) -> Result<()> {
+        let write_error = |e: std::io::Error| {
+            NetSuiteError::Authentication(format!("Failed to write token cache {}: {}", self.path.display(), e))
+        };
+
+        if let Some(parent) = self.path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            let mut builder = std::fs::DirBuilder::new();
+            builder.recursive(true);
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::DirBuilderExt;
+                builder.mode(0o700);
+            }
+            builder.create(parent).map_err(write_error)?;
+        }
+
+        let mut temp_path = self.path.clone().into_os_string();
+        temp_path.push(".tmp");
+        let temp_path = PathBuf::from(temp_path);
+
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+
+        let mut file = options.open(&temp_path).map_err(write_error)?;
+        // `mode` only applies when the file is created; a leftover temp file keeps its permissions
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            file.set_permissions(std::fs::Permissions::from_mode(0o600)).map_err(write_error)?;
+        }
+        serde_json::to_writer_pretty(&mut file, token)?;
+        file.sync_all().map_err(write_error)?;
+        drop(file);
+
+        std::fs::rename(&temp_path, &self.path).map_err(write_error)?;
+        Ok(())
+    }
+}
+
+/// OAuth 2.0 authorization code grant with PKCE: `login` runs the interactive flow,
+/// `access_token` serves the stored token and refreshes it when it expires
+pub struct AuthorizationCodeProvider {
+    oauth_client: BasicClient,
+    redirect_url: Url,
+    scopes: Vec<String>,
+    cache: TokenCache,
+    state: Mutex<TokenState>,
+}
+
+#[derive(Default)]
+struct TokenState {
+    token: Option<StoredToken>,
+    /// When the last refresh completed, so concurrent 401s share one refresh
+    refreshed_at: Option<Instant>,
+}
+
+impl AuthorizationCodeProvider {
+    pub fn new(config: &NetSuiteConfig) -> Result<Self> {
+        let redirect_url = Url::parse(&config.redirect_uri)?;
+
+        let oauth_client = BasicClient::new(
+            ClientId::new(config.consumer_key.clone()),
+            Some(ClientSecret::new(config.consumer_secret.clone())),
+            AuthUrl::new(config.authorize_url())?,
+            Some(TokenUrl::new(config.token_url())?)
+        )
+        .set_redirect_uri(RedirectUrl::from_url(redirect_url.clone()));
+
+        Ok(Self {
+            oauth_client,
+            redirect_url,
+            scopes: config.scopes.clone(),
+            cache: TokenCache::new(config.token_cache_path()),
+            state: Mutex::new(TokenState::default()),
+        })
+    }
+
+    /// Run the interactive login: hand the authorization URL to `present` (e.g. to print it or
+    /// open a browser), wait for the browser to hit the loopback redirect, exchange the code and
+    /// persist the resulting tokens
+    pub async fn login_with(&self, present: impl FnOnce(&str)) -> Result<StoredToken> {
+        let (pkce_challenge, pkce_verifier) = PkceCodeChallenge::new_random_sha256();
+        let (auth_url, csrf_token) = self.oauth_client
+            .authorize_url(CsrfToken::new_random)
+            .add_scopes(self.scopes.iter().cloned().map(Scope::new))
+            .set_pkce_challenge(pkce_challenge)
+            .url();
+
+        let address = format!(
+            "{}:{}",
+            self.redirect_url.host_str().unwrap_or("127.0.0.1"),
+            self.redirect_url.port_or_known_default().unwrap_or(80)
+        );
+        let listener = TcpListener::bind(&address)
+            .await
+            .map_err(|e| NetSuiteError::Authentication(format!("Failed to listen on {}: {}", address, e)))?;
+
+        present(auth_url.as_str());
+        info!("Waiting for the authorization redirect on {}", address);
+
+        let (code, state) = receive_redirect(&listener).await?;
+        if state.secret() != csrf_token.secret() {
+            return Err(NetSuiteError::Authentication(
+                "Authorization redirect carried an unexpected state parameter".to_string(),
+            ));
+        }
+
+        let response = self.oauth_client
+            .exchange_code(code)
+            .set_pkce_verifier(pkce_verifier)
+            .request_async(async_http_client)
+            .await
+            .map_err(|e| NetSuiteError::Authentication(format!("Failed to exchange authorization code: {}", e)))?;
+
+        let token = StoredToken::from_response(&response, None);
+        self.cache.save(&token)?;
+        self.state.lock().await.token = Some(token.clone());
+        info!("Tokens stored in {}", self.cache.path().display());
+
+        Ok(token)
+    }
+
+    /// Return a valid access token, loading it from the cache and refreshing it as needed
+    pub async fn access_token(&self) -> Result<String> {
+        let mut state = self.state.lock().await;
+        let current = self.current_token(&mut state)?;
+        if !current.is_expiring() {
+            return Ok(current.access_token);
+        }
+
+        let refreshed = self.refresh_token(current).await?;
+        let access_token = refreshed.access_token.clone();
+        state.token = Some(refreshed);
+        state.refreshed_at = Some(Instant::now());
+
+        Ok(access_token)
+    }
+
+    /// Force a refresh, e.g. after NetSuite rejected the current access token with 401.
+    /// Skipped if another task completed a refresh while this one waited for the lock.
+    pub async fn refresh(&self) -> Result<()> {
+        let requested = Instant::now();
+        let mut state = self.state.lock().await;
+        if state.refreshed_at.is_some_and(|refreshed_at| refreshed_at >= requested) {
+            debug!("Access token was refreshed concurrently; reusing it");
+            return Ok(());
+        }
+
+        let current = self.current_token(&mut state)?;
+        state.token = Some(self.refresh_token(current).await?);
+        state.refreshed_at = Some(Instant::now());
+        Ok(())
+    }
+
+    /// The token held in memory, loaded from the cache on first use
+    fn current_token(&self, state: &mut TokenState) -> Result<StoredToken> {
+        if state.token.is_none() {
+            state.token = self.cache.load()?;
+        }
+
+        state
+            .token
+            .clone()
+            .ok_or_else(|| NetSuiteError::Authentication("No stored OAuth 2.0 tokens; run `netsuite-client login` first".to_string()))
+    }
+
+    async fn refresh_token(&self, current: StoredToken) -> Result<StoredToken> {
+        let refresh_token = current
+            .refresh_token
+            .clone()
+            .ok_or_else(|| NetSuiteError::Authentication(
+                "Access token expired and no refresh token is stored; run `netsuite-client login` again".to_string(),
+            ))?;
+
+        debug!("Refreshing OAuth 2.0 access token");
+        let response = self.oauth_client
+            .exchange_refresh_token(&RefreshToken::new(refresh_token))
+            .request_async(async_http_client)
+            .await
+            .map_err(|e| NetSuiteError::Authentication(format!("Failed to refresh access token: {}", e)))?;
+
+        let token = StoredToken::from_response(&response, current.refresh_token);
+        self.cache.save(&token)?;
+
+        Ok(token)
+    }
+}
+
+/// Accept connections on the loopback listener until one carries the authorization code
+async fn receive_redirect(listener: &TcpListener) -> Result<(AuthorizationCode, CsrfToken)> {
+    loop {
+        let (mut stream, _) = listener.accept().await?;
+
+        let mut buffer = vec![0u8; 8192];
+        let read = stream.read(&mut buffer).await?;
+        let request = String::from_utf8_lossy(&buffer[..read]);
+
+        // Request line: GET /callback?code=...&state=... HTTP/1.1
+        let target = request
+            .lines()
+            .next()
+            .and_then(|line| line.split_whitespace().nth(1))
+            .unwrap_or("/");
+        let url = Url::parse(&format!("http://localhost{}", target))
+            .map_err(|e| NetSuiteError::Authentication(format!("Malformed authorization redirect: {}", e)))?;
+
+        let mut code = None;
+        let mut state = None;
+        let mut error = None;
+        for (key, value) in url.query_pairs() {
+            match key.as_ref() {
+                "code" => code = Some(value.into_owned()),
+                "state" => state = Some(value.into_owned()),
+                "error" => error = Some(value.into_owned()),
+                _ => {}
+            }
+        }
+
+        let message = match (&code, &error) {
+            (Some(_), _) => "Login complete. You can close this window.",
+            (None, Some(_)) => "Login failed. Check the terminal for details.",
+            // Favicon requests and the like; keep waiting
+            (None, None) => {
+                respond(&mut stream, "404 Not Found", "").await?;
+                continue;
+            }
+        };
+        respond(&mut stream, "200 OK", message).await?;
+
+        if let Some(error) = error {
+            return Err(NetSuiteError::Authentication(format!("Authorization failed: {}", error)));
+        }
+
+        return Ok((
+            AuthorizationCode::new(code.unwrap_or_default()),
+            CsrfToken::new(state.unwrap_or_default()),
+        ));
+    }
+}
+
+async fn respond(stream: &mut tokio::net::TcpStream, status: &str, body: &str) -> Result<()> {
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: text/plain; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    );
+    stream.write_all(response.as_bytes()).await?;
+    Ok(())
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::PermissionsExt;
+
+    fn mode(path: &Path) -> u32 {
+        std::fs::metadata(path).unwrap().permissions().mode() & 0o777
+    }
+
+    #[test]
+    fn save_restricts_permissions_and_replaces_existing_file() {
+        let dir = std::env::temp_dir().join(format!("netsuite-token-cache-{}", uuid::Uuid::new_v4()));
+        let cache = TokenCache::new(dir.join(".netsuite").join("tokens.json"));
+        let token = StoredToken { access_token: "access".to_string(), refresh_token: Some("refresh".to_string()), expires_at: 42 };
+
+        cache.save(&token).unwrap();
+        assert_eq!(mode(cache.path().parent().unwrap()), 0o700);
+        assert_eq!(mode(cache.path()), 0o600);
+
+        std::fs::set_permissions(cache.path(), std::fs::Permissions::from_mode(0o644)).unwrap();
+        cache.save(&StoredToken { access_token: "renewed".to_string(), ..token }).unwrap();
+        assert_eq!(mode(cache.path()), 0o600);
+        assert_eq!(cache.load().unwrap().unwrap().access_token, "renewed");
+        assert!(!cache.path().with_extension("json.tmp").exists());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,250 @@
+use crate::error::{NetSuiteError, Result};
+use jsonwebtoken::{encode, Algorithm, EncodingKey, Header};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::sync::Mutex;
+use tracing::debug;
+
+use crate::config::NetSuiteConfig;
+
+const CLIENT_ASSERTION_TYPE: &str = "urn:ietf:params:oauth:client-assertion-type:jwt-bearer";
+
+/// NetSuite rejects assertions that are valid for longer than an hour
+const ASSERTION_LIFETIME_SECS: u64 = 3600;
+
+/// Refresh this long before the cached token lapses so in-flight requests never carry an expired token
+const REFRESH_MARGIN: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Serialize)]
+struct AssertionClaims<'a> {
+    iss: &'a str,
+    scope: &'a [String],
+    aud: &'a str,
+    iat: u64,
+    exp: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    // NetSuite sends this as a string, other servers as a number
+    expires_in: serde_json::Value,
+}
+
+struct CachedToken {
+    access_token: String,
+    expires_at: Instant,
+}
+
+impl CachedToken {
+    /// Whether the token stays valid beyond the refresh margin
+    fn is_fresh(&self, now: Instant) -> bool {
+        now + REFRESH_MARGIN < self.expires_at
+    }
+}
+
+#[derive(Default)]
+struct TokenState {
+    token: Option<CachedToken>,
+    /// When the last token was obtained, so concurrent 401s drop the cached token only once
+    fetched_at: Option<Instant>,
+}
+
+/// OAuth 2.0 machine-to-machine (client credentials) flow: a JWT assertion signed with
+/// the certificate's private key is exchanged for a short-lived access token
+pub struct M2mTokenProvider {
+    http_client: Client,
+    token_url: String,
+    client_id: String,
+    certificate_id: String,
+    scopes: Vec<String>,
+    algorithm: Algorithm,
+    encoding_key: EncodingKey,
+    state: Mutex<TokenState>,
+}
+
+impl M2mTokenProvider {
+    pub fn new(config: &NetSuiteConfig, token_url: String, http_client: Client) -> Result<Self> {
+        let pem = std::fs::read(&config.private_key_path)
+            .map_err(|e| NetSuiteError::Authentication(format!("Failed to read private key {}: {}", config.private_key_path, e)))?;
+
+        // NetSuite accepts RSA (PS256) and EC (ES256) certificates
+        let (algorithm, encoding_key) = match EncodingKey::from_rsa_pem(&pem) {
+            Ok(key) => (Algorithm::PS256, key),
+            Err(_) => (
+                Algorithm::ES256,
+                EncodingKey::from_ec_pem(&pem)
+                    .map_err(|_| NetSuiteError::Authentication("Private key is neither RSA nor EC PEM".to_string()))?,
+            ),
+        };
+
+        Ok(Self {
+            http_client,
+            token_url,
+            client_id: config.consumer_key.clone(),
+            certificate_id: config.certificate_id.clone(),
+            scopes: config.scopes.clone(),
+            algorithm,
+            encoding_key,
+            state: Mutex::new(TokenState::default()),
+        })
+    }
+
+    /// Return a valid access token, requesting a new one if the cached token is missing or about to expire
+    pub async fn access_token(&self) -> Result<String> {
+        let mut state = self.state.lock().await;
+
+        if let Some(token) = state.token.as_ref().filter(|token| token.is_fresh(Instant::now())) {
+            return Ok(token.access_token.clone());
+        }
+
+        debug!("Requesting new M2M access token");
+        let token = self.request_token().await?;
+        let access_token = token.access_token.clone();
+        state.token = Some(token);
+        state.fetched_at = Some(Instant::now());
+
+        Ok(access_token)
+    }
+
+    /// Drop the cached token so the next call requests a fresh one.
+    /// Skipped if another task obtained a new token while this one waited for the lock.
+    pub async fn invalidate(&self) {
+        let requested = Instant::now();
+        let mut state = self.state.lock().await;
+        if state.fetched_at.is_some_and(|fetched_at| fetched_at >= requested) {
+            debug!("M2M access token was replaced concurrently; keeping it");
+            return;
+        }
+        state.token = None;
+    }
+
+    fn assertion(&self) -> Result<String> {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        let claims = AssertionClaims {
+            iss: &self.client_id,
+            scope: &self.scopes,
+            aud: &self.token_url,
+            iat: now,
+            exp: now + ASSERTION_LIFETIME_SECS,
+        };
+
+        let mut header = Header::new(self.algorithm);
+        header.kid = Some(self.certificate_id.clone());
+
+        encode(&header, &claims, &self.encoding_key)
+            .map_err(|e| NetSuiteError::Authentication(format!("Failed to sign client assertion: {}", e)))
+    }
+
+    async fn request_token(&self) -> Result<CachedToken> {
+        let assertion = self.assertion()?;
+        let params = [
+            ("grant_type", "client_credentials"),
+            ("client_assertion_type", CLIENT_ASSERTION_TYPE),
+            ("client_assertion", assertion.as_str()),
+        ];
+
+        let response = self.http_client
+            .post(&self.token_url)
+            .form(&params)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(NetSuiteError::from_response(response, "obtain M2M access token").await);
+        }
+
+        let token: TokenResponse = response.json().await?;
+        Ok(CachedToken {
+            expires_at: Instant::now() + Duration::from_secs(expires_in_secs(&token.expires_in)),
+            access_token: token.access_token,
+        })
+    }
+}
+
+/// Token lifetime in seconds, whether sent as a number or a string; an hour if unreadable
+fn expires_in_secs(expires_in: &serde_json::Value) -> u64 {
+    match expires_in {
+        serde_json::Value::Number(n) => n.as_u64(),
+        serde_json::Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+    .unwrap_or(ASSERTION_LIFETIME_SECS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::sync::Arc;
+
+    fn provider(token: Option<CachedToken>) -> M2mTokenProvider {
+        M2mTokenProvider {
+            http_client: Client::new(),
+            // Unroutable: any token request in these tests fails
+            token_url: "http://127.0.0.1:9/token".to_string(),
+            client_id: "client".to_string(),
+            certificate_id: "cert".to_string(),
+            scopes: vec!["rest_webservices".to_string()],
+            algorithm: Algorithm::HS256,
+            encoding_key: EncodingKey::from_secret(b"secret"),
+            state: Mutex::new(TokenState { token, fetched_at: None }),
+        }
+    }
+
+    fn token(access_token: &str, valid_for: Duration) -> CachedToken {
+        CachedToken { access_token: access_token.to_string(), expires_at: Instant::now() + valid_for }
+    }
+
+    #[test]
+    fn expires_in_accepts_numbers_and_strings() {
+        assert_eq!(expires_in_secs(&json!(3600)), 3600);
+        assert_eq!(expires_in_secs(&json!("1800")), 1800);
+        assert_eq!(expires_in_secs(&json!(" 900 ")), 900);
+        assert_eq!(expires_in_secs(&json!("soon")), ASSERTION_LIFETIME_SECS);
+        assert_eq!(expires_in_secs(&json!(null)), ASSERTION_LIFETIME_SECS);
+    }
+
+    #[test]
+    fn tokens_inside_the_refresh_margin_are_not_fresh() {
+        let now = Instant::now();
+        assert!(token("a", Duration::from_secs(600)).is_fresh(now));
+        assert!(!token("a", REFRESH_MARGIN - Duration::from_secs(1)).is_fresh(now));
+    }
+
+    #[tokio::test]
+    async fn fresh_cached_token_is_reused_without_a_request() {
+        let provider = provider(Some(token("cached", Duration::from_secs(600))));
+        assert_eq!(provider.access_token().await.unwrap(), "cached");
+        assert_eq!(provider.access_token().await.unwrap(), "cached");
+    }
+
+    #[tokio::test]
+    async fn expiring_token_is_replaced() {
+        let provider = provider(Some(token("stale", Duration::from_secs(30))));
+        // The token endpoint is unreachable, so reaching it shows the cached token was not used
+        assert!(provider.access_token().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn invalidate_keeps_a_token_obtained_while_it_waited() {
+        let provider = Arc::new(provider(Some(token("old", Duration::from_secs(600)))));
+
+        let mut state = provider.state.lock().await;
+        let invalidate = tokio::spawn({
+            let provider = provider.clone();
+            async move { provider.invalidate().await }
+        });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        state.token = Some(token("new", Duration::from_secs(600)));
+        state.fetched_at = Some(Instant::now());
+        drop(state);
+
+        invalidate.await.unwrap();
+        assert_eq!(provider.access_token().await.unwrap(), "new");
+
+        provider.invalidate().await;
+        assert!(provider.state.lock().await.token.is_none());
+    }
+}
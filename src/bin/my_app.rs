@@ -17,7 +17,7 @@ async fn main() -> Result<()> {
         }
         Err(e) => {
             error!("❌ Failed to load configuration: {}", e);
-            return Err(e);
+            return Err(e.into());
         }
     };
     
@@ -29,7 +29,7 @@ async fn main() -> Result<()> {
         }
         Err(e) => {
             error!("❌ Failed to initialize NetSuite client: {}", e);
-            return Err(e);
+            return Err(e.into());
         }
     };
     
@@ -51,8 +51,8 @@ async fn main() -> Result<()> {
             for (i, customer) in customers.iter().enumerate().take(3) {
                 info!("   {}. {} ({})", 
                     i + 1, 
-                    customer.entityid, 
-                    customer.companyname.as_deref().unwrap_or("No company name")
+                    customer.entity_id, 
+                    customer.company_name.as_deref().unwrap_or("No company name")
                 );
             }
         }
@@ -1,8 +1,9 @@
-use anyhow::Result;
+use chrono::NaiveDate;
 use crate::netsuite_client::NetSuiteClient;
 use crate::config::AppConfig;
 use tracing::{info, warn};
 use std::io::{self, Write};
+use crate::error::Result;
 
 pub struct Cli {
     client: NetSuiteClient,
@@ -31,6 +32,9 @@ impl Cli {
                 "help" => self.show_help(),
                 "test" => self.test_connection().await,
                 "customers" => self.list_customers().await,
+                "transactions" => self.list_transactions().await,
+                "vendor_payments" => self.list_vendor_payments().await,
+                "vendor_payments_custom" => self.list_vendor_payments_custom().await,
                 "quit" | "exit" => {
                     info!("👋 Goodbye!");
                     break;
@@ -77,8 +81,8 @@ impl Cli {
                 for (i, customer) in customers.iter().enumerate().take(5) {
                     println!("  {}. {} ({})", 
                         i + 1, 
-                        customer.entityid, 
-                        customer.companyname.as_deref().unwrap_or("No company name")
+                        customer.entity_id, 
+                        customer.company_name.as_deref().unwrap_or("No company name")
                     );
                 }
                 if customers.len() > 5 {
@@ -94,7 +98,7 @@ impl Cli {
     
     async fn list_transactions(&self) {
         info!("Fetching recent transactions...");
-        match self.client.get_transactions_with_filters(&[], Some(10)).await {
+        match self.client.get_transactions_with_filters(None, Some(10)).await {
             Ok(transactions) => {
                 info!("✅ Successfully fetched {} transactions", transactions.len());
                 for (i, transaction) in transactions.iter().enumerate().take(5) {
@@ -103,7 +107,7 @@ impl Cli {
                         transaction.id,
                         transaction.transaction_type.as_deref().unwrap_or("Unknown"),
                         transaction.amount.unwrap_or(0.0),
-                        transaction.createddate.as_deref().unwrap_or("No date")
+                        transaction.created_date.as_deref().unwrap_or("No date")
                     );
                 }
                 if transactions.len() > 5 {
@@ -120,19 +124,20 @@ impl Cli {
     async fn list_vendor_payments(&self) {
         info!("Fetching vendor payments for May-Aug 2024...");
         match self.client.get_vendor_payments_2024(Some(20)).await {
-            Ok(transactions) => {
-                info!("✅ Successfully fetched {} vendor payments", transactions.len());
-                for (i, transaction) in transactions.iter().enumerate().take(5) {
-                    println!("  {}. {} - ${:.2} - {} ({})", 
+            Ok(payments) => {
+                info!("✅ Successfully fetched {} vendor payments", payments.len());
+                for (i, payment) in payments.iter().enumerate().take(5) {
+                    println!("  {}. {} - ${:.2} - {} ({}) - {} bills", 
                         i + 1, 
-                        transaction.id,
-                        transaction.amount.unwrap_or(0.0),
-                        transaction.memo.as_deref().unwrap_or("No memo"),
-                        transaction.createddate.as_deref().unwrap_or("No date")
+                        payment.id.as_deref().unwrap_or("-"),
+                        payment.total.unwrap_or(0.0),
+                        payment.memo.as_deref().unwrap_or("No memo"),
+                        payment.created_date.as_deref().unwrap_or("No date"),
+                        payment.settlements().len()
                     );
                 }
-                if transactions.len() > 5 {
-                    println!("  ... and {} more", transactions.len() - 5);
+                if payments.len() > 5 {
+                    println!("  ... and {} more", payments.len() - 5);
                 }
             }
             Err(e) => {
@@ -146,25 +151,26 @@ impl Cli {
         info!("Fetching vendor payments for custom date range...");
         
         // Example: Get vendor payments for Q1 2024
-        let start_date = "2024-01-01";
-        let end_date = "2024-03-31";
+        let start_date = NaiveDate::from_ymd_opt(2024, 1, 1).expect("valid date");
+        let end_date = NaiveDate::from_ymd_opt(2024, 3, 31).expect("valid date");
         
         info!("Date range: {} to {}", start_date, end_date);
         
         match self.client.get_vendor_payments(start_date, end_date, Some(20)).await {
-            Ok(transactions) => {
-                info!("✅ Successfully fetched {} vendor payments", transactions.len());
-                for (i, transaction) in transactions.iter().enumerate().take(5) {
-                    println!("  {}. {} - ${:.2} - {} ({})", 
+            Ok(payments) => {
+                info!("✅ Successfully fetched {} vendor payments", payments.len());
+                for (i, payment) in payments.iter().enumerate().take(5) {
+                    println!("  {}. {} - ${:.2} - {} ({}) - {} bills", 
                         i + 1, 
-                        transaction.id,
-                        transaction.amount.unwrap_or(0.0),
-                        transaction.memo.as_deref().unwrap_or("No memo"),
-                        transaction.createddate.as_deref().unwrap_or("No date")
+                        payment.id.as_deref().unwrap_or("-"),
+                        payment.total.unwrap_or(0.0),
+                        payment.memo.as_deref().unwrap_or("No memo"),
+                        payment.created_date.as_deref().unwrap_or("No date"),
+                        payment.settlements().len()
                     );
                 }
-                if transactions.len() > 5 {
-                    println!("  ... and {} more", transactions.len() - 5);
+                if payments.len() > 5 {
+                    println!("  ... and {} more", payments.len() - 5);
                 }
             }
             Err(e) => {
@@ -1,7 +1,10 @@
-use anyhow::Result;
 use config::{Config as ConfigBuilder, Environment, File};
 use serde::Deserialize;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+
+use crate::error::Result;
+use crate::rate_limit::RateLimitConfig;
+use crate::retry::RetryPolicy;
 
 #[derive(Debug, Deserialize)]
 pub struct AppConfig {
@@ -15,7 +18,105 @@ pub struct NetSuiteConfig {
     pub consumer_secret: String,
     pub token_id: String,
     pub token_secret: String,
-    pub base_url: String,
+    /// Override for the REST domain; derived from `account_id` when unset or empty
+    #[serde(default)]
+    pub base_url: Option<String>,
+    /// Which authentication scheme signs outgoing requests
+    #[serde(default)]
+    pub auth_method: AuthMethod,
+    /// Certificate ID from the OAuth 2.0 client credentials (M2M) setup
+    #[serde(default)]
+    pub certificate_id: String,
+    /// PEM file holding the private key of the M2M certificate
+    #[serde(default)]
+    pub private_key_path: String,
+    /// OAuth 2.0 scopes requested for access tokens
+    #[serde(default = "default_scopes")]
+    pub scopes: Vec<String>,
+    /// Loopback redirect URI registered on the integration record for `login`
+    #[serde(default = "default_redirect_uri")]
+    pub redirect_uri: String,
+    /// Where `login` stores access and refresh tokens; defaults to `~/.netsuite/tokens.json`
+    #[serde(default)]
+    pub token_cache_path: String,
+    /// Requests allowed in flight at once across everything sharing the client; 0 means unlimited
+    #[serde(default = "default_max_concurrent_requests")]
+    pub max_concurrent_requests: usize,
+    /// Optional per-period call budget; unlimited when absent
+    #[serde(default)]
+    pub rate_limit: Option<RateLimitConfig>,
+    /// Backoff for throttled and transiently failing requests
+    #[serde(default)]
+    pub retry: RetryPolicy,
+}
+
+/// Authentication scheme used by the client
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuthMethod {
+    /// Token-Based Authentication (OAuth 1.0a)
+    #[default]
+    Tba,
+    /// OAuth 2.0 client credentials with a JWT certificate assertion
+    M2m,
+    /// OAuth 2.0 authorization code + PKCE, using tokens stored by `netsuite-client login`
+    AuthorizationCode,
+}
+
+fn default_scopes() -> Vec<String> {
+    vec!["rest_webservices".to_string()]
+}
+
+fn default_max_concurrent_requests() -> usize {
+    5
+}
+
+fn default_redirect_uri() -> String {
+    "http://127.0.0.1:8910/callback".to_string()
+}
+
+impl NetSuiteConfig {
+    /// Lowercase, hyphenated account ID as used in NetSuite hostnames (`1234567_SB1` -> `1234567-sb1`)
+    pub fn account_host_id(&self) -> String {
+        self.account_id.trim().to_lowercase().replace('_', "-")
+    }
+
+    /// Uppercase, underscored account ID as used for the OAuth realm (`1234567-sb1` -> `1234567_SB1`)
+    pub fn realm(&self) -> String {
+        self.account_id.trim().to_uppercase().replace('-', "_")
+    }
+
+    /// Account-specific SuiteTalk domain, unless `base_url` overrides it
+    pub fn base_url(&self) -> String {
+        match self.base_url.as_deref().map(str::trim) {
+            Some(url) if !url.is_empty() => url.trim_end_matches('/').to_string(),
+            _ => format!("https://{}.suitetalk.api.netsuite.com", self.account_host_id()),
+        }
+    }
+
+    /// Root of all REST web services paths (`record/v1/...`, `query/v1/...`, `auth/oauth2/...`)
+    pub fn rest_url(&self) -> String {
+        format!("{}/services/rest", self.base_url())
+    }
+
+    /// OAuth 2.0 token endpoint
+    pub fn token_url(&self) -> String {
+        format!("{}/auth/oauth2/v1/token", self.rest_url())
+    }
+
+    /// OAuth 2.0 authorization endpoint, served from the account's UI domain
+    pub fn authorize_url(&self) -> String {
+        format!("https://{}.app.netsuite.com/app/login/oauth2/authorize.nl", self.account_host_id())
+    }
+
+    /// Resolved location of the token cache file
+    pub fn token_cache_path(&self) -> PathBuf {
+        if !self.token_cache_path.is_empty() {
+            return PathBuf::from(&self.token_cache_path);
+        }
+        let home = std::env::var_os("HOME").unwrap_or_else(|| ".".into());
+        PathBuf::from(home).join(".netsuite").join("tokens.json")
+    }
 }
 
 impl AppConfig {
@@ -45,7 +146,49 @@ impl Default for NetSuiteConfig {
             consumer_secret: "".to_string(),
             token_id: "".to_string(),
             token_secret: "".to_string(),
-            base_url: "https://rest.na1.netsuite.com".to_string(),
+            base_url: None,
+            auth_method: AuthMethod::default(),
+            certificate_id: "".to_string(),
+            private_key_path: "".to_string(),
+            scopes: default_scopes(),
+            redirect_uri: default_redirect_uri(),
+            token_cache_path: "".to_string(),
+            max_concurrent_requests: default_max_concurrent_requests(),
+            rate_limit: None,
+            retry: RetryPolicy::default(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use config::FileFormat;
+
+    fn parse(toml: &str) -> std::result::Result<AppConfig, config::ConfigError> {
+        ConfigBuilder::builder()
+            .add_source(File::from_str(toml, FileFormat::Toml))
+            .build()?
+            .try_deserialize()
+    }
+
+    #[test]
+    fn uncommenting_base_url_in_default_toml_overrides_it() {
+        let toml = include_str!("../config/default.toml")
+            .replace("# base_url = ", "base_url = ")
+            .replace("# [netsuite.rate_limit]", "[netsuite.rate_limit]")
+            .replace("# requests_per_minute = ", "requests_per_minute = ");
+
+        let config = parse(&toml).unwrap();
+        assert_eq!(config.netsuite.base_url(), "https://1234567.suitetalk.api.netsuite.com");
+        assert_eq!(config.netsuite.rate_limit.map(|limit| limit.requests_per_minute), Some(100));
+        assert_eq!(config.netsuite.retry.max_attempts, 4);
+    }
+
+    #[test]
+    fn unknown_retry_keys_are_rejected() {
+        let toml = "[netsuite]\naccount_id = \"1\"\nconsumer_key = \"\"\nconsumer_secret = \"\"\n\
+                    token_id = \"\"\ntoken_secret = \"\"\n[netsuite.retry]\nbase_url = \"https://example.com\"\n";
+        assert!(parse(toml).is_err());
+    }
+}
@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::netsuite_client::{EntityReference, Link};
+use crate::record::Record;
+
+/// A vendor as returned by `record/v1/vendor`
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Vendor {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external_id: Option<String>,
+    /// Vendor name/ID as shown in the UI
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entity_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub company_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub is_person: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub phone: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subsidiary: Option<EntityReference>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub currency: Option<EntityReference>,
+    /// Payment terms, e.g. `Net 30`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub terms: Option<EntityReference>,
+    /// Tax ID / VAT registration number
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tax_id_num: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub is_inactive: Option<bool>,
+    /// Open payables balance; set by NetSuite
+    #[serde(default, skip_serializing)]
+    pub balance: Option<f64>,
+    #[serde(default, skip_serializing)]
+    pub date_created: Option<String>,
+    #[serde(flatten, with = "crate::record::custom_fields")]
+    pub custom_fields: BTreeMap<String, serde_json::Value>,
+    #[serde(default, skip_serializing)]
+    pub links: Vec<Link>,
+}
+
+impl Record for Vendor {
+    const RECORD_TYPE: &'static str = "vendor";
+    type Id = String;
+
+    fn id(&self) -> Option<String> {
+        self.id.clone()
+    }
+}
@@ -0,0 +1,266 @@
+use reqwest::{header::InvalidHeaderValue, Response, StatusCode};
+use serde::Deserialize;
+use std::fmt;
+use std::time::Duration;
+
+use crate::retry;
+
+/// Result type used throughout the library
+pub type Result<T, E = NetSuiteError> = std::result::Result<T, E>;
+
+/// Errors returned by the client. API failures carry NetSuite's parsed error body;
+/// match on the variant to handle the common cases.
+#[derive(Debug, thiserror::Error)]
+pub enum NetSuiteError {
+    /// 401: credentials were missing, expired or rejected
+    #[error("Unauthorized: {0}")]
+    Unauthorized(ApiError),
+    /// 403: authenticated, but the role lacks permission
+    #[error("Forbidden: {0}")]
+    Forbidden(ApiError),
+    /// 404 or `NONEXISTENT_ID`: the record or endpoint does not exist
+    #[error("Not found: {0}")]
+    NotFound(ApiError),
+    /// 429: request or concurrency limit exceeded
+    #[error("Rate limited: {error}")]
+    RateLimited {
+        /// Delay requested by the `Retry-After` header, if any
+        retry_after: Option<Duration>,
+        error: ApiError,
+    },
+    /// A field in the request was unknown or had an invalid value; see `ApiError::error_path`
+    #[error("Invalid field: {0}")]
+    InvalidField(ApiError),
+    /// The record was changed by someone else since it was read
+    #[error("Concurrent modification: {0}")]
+    Concurrency(ApiError),
+    /// Any other error response from NetSuite
+    #[error("NetSuite error: {0}")]
+    Api(ApiError),
+    /// Obtaining or signing credentials failed before a request could be sent
+    #[error("Authentication failed: {0}")]
+    Authentication(String),
+    /// The request was rejected locally, e.g. an invalid SuiteQL identifier or transform pair
+    #[error("Invalid request: {0}")]
+    InvalidRequest(String),
+    /// NetSuite answered successfully but not in the expected shape
+    #[error("Invalid response: {0}")]
+    InvalidResponse(String),
+    /// An async job did not finish in time
+    #[error("Timed out: {0}")]
+    Timeout(String),
+    #[error("Configuration error: {0}")]
+    Config(#[from] config::ConfigError),
+    #[error("HTTP error: {0}")]
+    Http(#[from] reqwest::Error),
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// NetSuite's JSON error body, plus the HTTP status and what the client was doing
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ApiError {
+    /// HTTP status of the response
+    #[serde(skip)]
+    pub status: u16,
+    /// What the client was doing, e.g. `fetch customer`
+    #[serde(skip)]
+    pub context: String,
+    /// Link to the error type documentation
+    #[serde(rename = "type", default)]
+    pub error_type: Option<String>,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(rename = "o:errorDetails", default)]
+    pub details: Vec<ErrorDetail>,
+}
+
+/// One entry of `o:errorDetails`
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ErrorDetail {
+    #[serde(default)]
+    pub detail: String,
+    /// e.g. `INVALID_FIELD`, `NONEXISTENT_ID`, `RCRD_HAS_BEEN_CHANGED`
+    #[serde(rename = "o:errorCode", default)]
+    pub error_code: Option<String>,
+    /// Field the error refers to, e.g. `entity` or `item.items[0].quantity`
+    #[serde(rename = "o:errorPath", default)]
+    pub error_path: Option<String>,
+}
+
+impl ApiError {
+    /// Error code of the first detail
+    pub fn error_code(&self) -> Option<&str> {
+        self.details.iter().find_map(|d| d.error_code.as_deref())
+    }
+
+    /// Error path of the first detail that has one
+    pub fn error_path(&self) -> Option<&str> {
+        self.details.iter().find_map(|d| d.error_path.as_deref())
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "failed to {} ({}", self.context, self.status)?;
+        if let Some(title) = &self.title {
+            write!(f, " {}", title)?;
+        }
+        write!(f, ")")?;
+        for detail in &self.details {
+            write!(f, ": {}", detail.detail)?;
+            if let Some(code) = &detail.error_code {
+                write!(f, " [{}]", code)?;
+            }
+            if let Some(path) = &detail.error_path {
+                write!(f, " at {}", path)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl From<InvalidHeaderValue> for NetSuiteError {
+    fn from(error: InvalidHeaderValue) -> Self {
+        Self::InvalidRequest(format!("Invalid header value: {}", error))
+    }
+}
+
+impl From<oauth2::url::ParseError> for NetSuiteError {
+    fn from(error: oauth2::url::ParseError) -> Self {
+        Self::InvalidRequest(format!("Invalid URL: {}", error))
+    }
+}
+
+impl NetSuiteError {
+    /// Consume an error response and classify it. `context` describes the failed action, e.g. `fetch customer`.
+    pub async fn from_response(response: Response, context: impl Into<String>) -> Self {
+        let status = response.status();
+        let retry_after = retry::retry_after(response.headers());
+
+        match response.text().await {
+            Ok(body) => Self::from_parts(status, retry_after, &body, context),
+            Err(e) => Self::Http(e),
+        }
+    }
+
+    /// Classify an error from its status, `Retry-After` delay and raw body.
+    /// Bodies that are not NetSuite JSON errors are kept as the detail text.
+    pub fn from_parts(status: StatusCode, retry_after: Option<Duration>, body: &str, context: impl Into<String>) -> Self {
+        let mut error = serde_json::from_str::<ApiError>(body).unwrap_or_else(|_| ApiError {
+            details: vec![ErrorDetail { detail: body.trim().to_string(), ..Default::default() }]
+                .into_iter()
+                .filter(|d| !d.detail.is_empty())
+                .collect(),
+            ..Default::default()
+        });
+        error.status = status.as_u16();
+        error.context = context.into();
+
+        match (status, error.error_code()) {
+            (StatusCode::TOO_MANY_REQUESTS, _) => Self::RateLimited { retry_after, error },
+            (StatusCode::UNAUTHORIZED, _) => Self::Unauthorized(error),
+            (StatusCode::FORBIDDEN, _) => Self::Forbidden(error),
+            (StatusCode::NOT_FOUND, _) | (_, Some("NONEXISTENT_ID")) => Self::NotFound(error),
+            (StatusCode::CONFLICT | StatusCode::PRECONDITION_FAILED, _) | (_, Some("RCRD_HAS_BEEN_CHANGED")) => {
+                Self::Concurrency(error)
+            }
+            (_, Some(code)) if code.starts_with("INVALID_") || (code == "USER_ERROR" && error.error_path().is_some()) => {
+                Self::InvalidField(error)
+            }
+            _ => Self::Api(error),
+        }
+    }
+
+    /// The parsed NetSuite error body, for errors that came from an API response
+    pub fn api_error(&self) -> Option<&ApiError> {
+        match self {
+            Self::Unauthorized(error)
+            | Self::Forbidden(error)
+            | Self::NotFound(error)
+            | Self::RateLimited { error, .. }
+            | Self::InvalidField(error)
+            | Self::Concurrency(error)
+            | Self::Api(error) => Some(error),
+            _ => None,
+        }
+    }
+
+    /// HTTP status of the failed response, if there was one
+    pub fn status(&self) -> Option<StatusCode> {
+        self.api_error().and_then(|error| StatusCode::from_u16(error.status).ok())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INVALID_FIELD_BODY: &str = r#"{
+        "type": "https://www.rfc-editor.org/rfc/rfc9110.html#section-15.5.1",
+        "title": "Bad Request",
+        "status": 400,
+        "o:errorDetails": [
+            {
+                "detail": "Invalid value for field quantity.",
+                "o:errorPath": "item.items[0].quantity",
+                "o:errorCode": "INVALID_FLD_VALUE"
+            }
+        ]
+    }"#;
+
+    fn error_body(code: &str) -> String {
+        format!(r#"{{"title": "Error", "o:errorDetails": [{{"detail": "failed", "o:errorCode": "{}"}}]}}"#, code)
+    }
+
+    #[test]
+    fn parses_error_details() {
+        let error = NetSuiteError::from_parts(StatusCode::BAD_REQUEST, None, INVALID_FIELD_BODY, "create salesorder");
+        let NetSuiteError::InvalidField(api) = &error else {
+            panic!("expected InvalidField, got {:?}", error);
+        };
+
+        assert_eq!(api.status, 400);
+        assert_eq!(api.title.as_deref(), Some("Bad Request"));
+        assert_eq!(api.error_code(), Some("INVALID_FLD_VALUE"));
+        assert_eq!(api.error_path(), Some("item.items[0].quantity"));
+        assert_eq!(error.status(), Some(StatusCode::BAD_REQUEST));
+        assert_eq!(
+            api.to_string(),
+            "failed to create salesorder (400 Bad Request): Invalid value for field quantity. [INVALID_FLD_VALUE] at item.items[0].quantity"
+        );
+    }
+
+    #[test]
+    fn classifies_by_status_and_code() {
+        let classify = |status: u16, body: &str| {
+            NetSuiteError::from_parts(StatusCode::from_u16(status).unwrap(), Some(Duration::from_secs(3)), body, "fetch customer")
+        };
+
+        assert!(matches!(
+            classify(429, &error_body("CONCURRENCY_LIMIT_EXCEEDED")),
+            NetSuiteError::RateLimited { retry_after: Some(d), .. } if d == Duration::from_secs(3)
+        ));
+        assert!(matches!(classify(401, "{}"), NetSuiteError::Unauthorized(_)));
+        assert!(matches!(classify(403, "{}"), NetSuiteError::Forbidden(_)));
+        assert!(matches!(classify(404, "{}"), NetSuiteError::NotFound(_)));
+        assert!(matches!(classify(400, &error_body("NONEXISTENT_ID")), NetSuiteError::NotFound(_)));
+        assert!(matches!(classify(400, &error_body("RCRD_HAS_BEEN_CHANGED")), NetSuiteError::Concurrency(_)));
+        assert!(matches!(classify(412, "{}"), NetSuiteError::Concurrency(_)));
+        assert!(matches!(classify(400, &error_body("USER_ERROR")), NetSuiteError::Api(_)));
+        assert!(matches!(classify(500, "{}"), NetSuiteError::Api(_)));
+    }
+
+    #[test]
+    fn keeps_non_json_bodies_as_detail() {
+        let error = NetSuiteError::from_parts(StatusCode::BAD_GATEWAY, None, "  upstream timed out \n", "list customer");
+        let api = error.api_error().unwrap();
+        assert_eq!(api.details.len(), 1);
+        assert_eq!(api.details[0].detail, "upstream timed out");
+
+        let empty = NetSuiteError::from_parts(StatusCode::BAD_GATEWAY, None, "", "list customer");
+        assert!(empty.api_error().unwrap().details.is_empty());
+    }
+}
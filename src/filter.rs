@@ -0,0 +1,373 @@
+use chrono::NaiveDate;
+use std::fmt;
+
+use crate::error::{NetSuiteError, Result};
+
+/// Date format NetSuite accepts in record collection filters
+const DATE_FORMAT: &str = "%m/%d/%Y";
+
+/// Operators supported by the REST record collection `q=` parameter
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    Is,
+    IsNot,
+    AnyOf,
+    NoneOf,
+    Between,
+    StartWith,
+    Contain,
+    Empty,
+    EmptyNot,
+    Greater,
+    GreaterOrEqual,
+    Less,
+    LessOrEqual,
+    On,
+    Before,
+    After,
+    OnOrBefore,
+    OnOrAfter,
+}
+
+impl Operator {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Operator::Is => "IS",
+            Operator::IsNot => "IS_NOT",
+            Operator::AnyOf => "ANY_OF",
+            Operator::NoneOf => "NONE_OF",
+            Operator::Between => "BETWEEN",
+            Operator::StartWith => "START_WITH",
+            Operator::Contain => "CONTAIN",
+            Operator::Empty => "EMPTY",
+            Operator::EmptyNot => "EMPTY_NOT",
+            Operator::Greater => "GREATER",
+            Operator::GreaterOrEqual => "GREATER_OR_EQUAL",
+            Operator::Less => "LESS",
+            Operator::LessOrEqual => "LESS_OR_EQUAL",
+            Operator::On => "ON",
+            Operator::Before => "BEFORE",
+            Operator::After => "AFTER",
+            Operator::OnOrBefore => "ON_OR_BEFORE",
+            Operator::OnOrAfter => "ON_OR_AFTER",
+        }
+    }
+}
+
+/// A literal on the right-hand side of a filter condition
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterValue {
+    Text(String),
+    Integer(i64),
+    Number(f64),
+    Bool(bool),
+    Date(NaiveDate),
+}
+
+impl fmt::Display for FilterValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FilterValue::Text(text) => {
+                write!(f, "\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+            }
+            FilterValue::Integer(n) => write!(f, "{}", n),
+            FilterValue::Number(n) => write!(f, "{}", n),
+            FilterValue::Bool(b) => write!(f, "{}", b),
+            FilterValue::Date(date) => write!(f, "\"{}\"", date.format(DATE_FORMAT)),
+        }
+    }
+}
+
+impl From<&str> for FilterValue {
+    fn from(value: &str) -> Self {
+        FilterValue::Text(value.to_string())
+    }
+}
+
+impl From<String> for FilterValue {
+    fn from(value: String) -> Self {
+        FilterValue::Text(value)
+    }
+}
+
+impl From<i64> for FilterValue {
+    fn from(value: i64) -> Self {
+        FilterValue::Integer(value)
+    }
+}
+
+impl From<i32> for FilterValue {
+    fn from(value: i32) -> Self {
+        FilterValue::Integer(value.into())
+    }
+}
+
+impl From<u32> for FilterValue {
+    fn from(value: u32) -> Self {
+        FilterValue::Integer(value.into())
+    }
+}
+
+impl From<f64> for FilterValue {
+    fn from(value: f64) -> Self {
+        FilterValue::Number(value)
+    }
+}
+
+impl From<bool> for FilterValue {
+    fn from(value: bool) -> Self {
+        FilterValue::Bool(value)
+    }
+}
+
+impl From<NaiveDate> for FilterValue {
+    fn from(value: NaiveDate) -> Self {
+        FilterValue::Date(value)
+    }
+}
+
+/// A typed expression for the REST `q=` parameter.
+///
+/// `Filter::field("type").is("VendPymt").and(Filter::field("createddate").between(may_1, aug_31))`
+/// renders as `type IS "VendPymt" AND createddate BETWEEN ["05/01/2024", "08/31/2024"]`.
+/// Mixed AND/OR groups are parenthesized; the client URL-encodes the whole expression into a single `q`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Filter {
+    Condition {
+        field: String,
+        operator: Operator,
+        values: Vec<FilterValue>,
+    },
+    And(Vec<Filter>),
+    Or(Vec<Filter>),
+}
+
+/// Left-hand side of a condition; finish it with an operator method
+pub struct FieldFilter {
+    field: String,
+}
+
+impl Filter {
+    pub fn field(name: impl Into<String>) -> FieldFilter {
+        FieldFilter { field: name.into() }
+    }
+
+    /// Combine with another filter; both must match
+    pub fn and(self, other: Filter) -> Filter {
+        match self {
+            Filter::And(mut filters) => {
+                filters.push(other);
+                Filter::And(filters)
+            }
+            filter => Filter::And(vec![filter, other]),
+        }
+    }
+
+    /// Combine with another filter; either may match
+    pub fn or(self, other: Filter) -> Filter {
+        match self {
+            Filter::Or(mut filters) => {
+                filters.push(other);
+                Filter::Or(filters)
+            }
+            filter => Filter::Or(vec![filter, other]),
+        }
+    }
+
+    /// All of the given filters must match
+    pub fn all(filters: impl IntoIterator<Item = Filter>) -> Filter {
+        Filter::And(filters.into_iter().collect())
+    }
+
+    /// Any of the given filters may match
+    pub fn any(filters: impl IntoIterator<Item = Filter>) -> Filter {
+        Filter::Or(filters.into_iter().collect())
+    }
+
+    /// Render the expression for `q=`, rejecting NaN and infinite numbers, which NetSuite cannot parse
+    pub fn to_query(&self) -> Result<String> {
+        self.check_values()?;
+        Ok(self.to_string())
+    }
+
+    fn check_values(&self) -> Result<()> {
+        match self {
+            Filter::Condition { field, values, .. } => match values.iter().find(|value| matches!(value, FilterValue::Number(n) if !n.is_finite())) {
+                Some(value) => Err(NetSuiteError::InvalidRequest(format!("Filter on {} compares against non-finite number {}", field, value))),
+                None => Ok(()),
+            },
+            Filter::And(filters) | Filter::Or(filters) => filters.iter().try_for_each(Filter::check_values),
+        }
+    }
+
+    fn fmt_group(&self, f: &mut fmt::Formatter<'_>, nested: bool) -> fmt::Result {
+        let (filters, joiner) = match self {
+            Filter::And(filters) => (filters, " AND "),
+            Filter::Or(filters) => (filters, " OR "),
+            Filter::Condition { .. } => return write!(f, "{}", self),
+        };
+
+        let wrap = nested && filters.len() > 1;
+        if wrap {
+            write!(f, "(")?;
+        }
+        for (i, filter) in filters.iter().enumerate() {
+            if i > 0 {
+                write!(f, "{}", joiner)?;
+            }
+            filter.fmt_group(f, true)?;
+        }
+        if wrap {
+            write!(f, ")")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Filter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Filter::Condition { field, operator, values } => {
+                write!(f, "{} {}", field, operator.as_str())?;
+                match (operator, values.as_slice()) {
+                    (_, []) => Ok(()),
+                    (Operator::AnyOf | Operator::NoneOf | Operator::Between, values) => {
+                        let list = values.iter().map(ToString::to_string).collect::<Vec<_>>();
+                        write!(f, " [{}]", list.join(", "))
+                    }
+                    (_, [value, ..]) => write!(f, " {}", value),
+                }
+            }
+            group => group.fmt_group(f, false),
+        }
+    }
+}
+
+impl FieldFilter {
+    fn condition(self, operator: Operator, values: Vec<FilterValue>) -> Filter {
+        Filter::Condition { field: self.field, operator, values }
+    }
+
+    pub fn is(self, value: impl Into<FilterValue>) -> Filter {
+        self.condition(Operator::Is, vec![value.into()])
+    }
+
+    pub fn is_not(self, value: impl Into<FilterValue>) -> Filter {
+        self.condition(Operator::IsNot, vec![value.into()])
+    }
+
+    pub fn any_of<V: Into<FilterValue>>(self, values: impl IntoIterator<Item = V>) -> Filter {
+        self.condition(Operator::AnyOf, values.into_iter().map(Into::into).collect())
+    }
+
+    pub fn none_of<V: Into<FilterValue>>(self, values: impl IntoIterator<Item = V>) -> Filter {
+        self.condition(Operator::NoneOf, values.into_iter().map(Into::into).collect())
+    }
+
+    pub fn between(self, from: impl Into<FilterValue>, to: impl Into<FilterValue>) -> Filter {
+        self.condition(Operator::Between, vec![from.into(), to.into()])
+    }
+
+    pub fn start_with(self, value: impl Into<String>) -> Filter {
+        self.condition(Operator::StartWith, vec![FilterValue::Text(value.into())])
+    }
+
+    pub fn contain(self, value: impl Into<String>) -> Filter {
+        self.condition(Operator::Contain, vec![FilterValue::Text(value.into())])
+    }
+
+    pub fn empty(self) -> Filter {
+        self.condition(Operator::Empty, Vec::new())
+    }
+
+    pub fn empty_not(self) -> Filter {
+        self.condition(Operator::EmptyNot, Vec::new())
+    }
+
+    pub fn greater(self, value: impl Into<FilterValue>) -> Filter {
+        self.condition(Operator::Greater, vec![value.into()])
+    }
+
+    pub fn greater_or_equal(self, value: impl Into<FilterValue>) -> Filter {
+        self.condition(Operator::GreaterOrEqual, vec![value.into()])
+    }
+
+    pub fn less(self, value: impl Into<FilterValue>) -> Filter {
+        self.condition(Operator::Less, vec![value.into()])
+    }
+
+    pub fn less_or_equal(self, value: impl Into<FilterValue>) -> Filter {
+        self.condition(Operator::LessOrEqual, vec![value.into()])
+    }
+
+    pub fn on(self, date: NaiveDate) -> Filter {
+        self.condition(Operator::On, vec![date.into()])
+    }
+
+    pub fn before(self, date: NaiveDate) -> Filter {
+        self.condition(Operator::Before, vec![date.into()])
+    }
+
+    pub fn after(self, date: NaiveDate) -> Filter {
+        self.condition(Operator::After, vec![date.into()])
+    }
+
+    pub fn on_or_before(self, date: NaiveDate) -> Filter {
+        self.condition(Operator::OnOrBefore, vec![date.into()])
+    }
+
+    pub fn on_or_after(self, date: NaiveDate) -> Filter {
+        self.condition(Operator::OnOrAfter, vec![date.into()])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn renders_conditions_and_value_lists() {
+        assert_eq!(Filter::field("type").is("VendPymt").to_string(), r#"type IS "VendPymt""#);
+        assert_eq!(Filter::field("entity").any_of([12, 34]).to_string(), "entity ANY_OF [12, 34]");
+        assert_eq!(Filter::field("amount").greater(9.5).to_string(), "amount GREATER 9.5");
+        assert_eq!(Filter::field("isinactive").is(false).to_string(), "isinactive IS false");
+        assert_eq!(Filter::field("email").empty().to_string(), "email EMPTY");
+    }
+
+    #[test]
+    fn formats_dates_month_first() {
+        let filter = Filter::field("createddate").between(date(2024, 5, 1), date(2024, 8, 31));
+        assert_eq!(filter.to_string(), r#"createddate BETWEEN ["05/01/2024", "08/31/2024"]"#);
+        assert_eq!(Filter::field("trandate").on_or_after(date(2024, 1, 9)).to_string(), r#"trandate ON_OR_AFTER "01/09/2024""#);
+    }
+
+    #[test]
+    fn escapes_quotes_and_backslashes() {
+        let filter = Filter::field("companyName").start_with(r#"Smith "&" Sons\"#);
+        assert_eq!(filter.to_string(), r#"companyName START_WITH "Smith \"&\" Sons\\""#);
+    }
+
+    #[test]
+    fn parenthesizes_nested_groups_only() {
+        let filter = Filter::field("type")
+            .is("SalesOrd")
+            .and(Filter::field("status").is("A").or(Filter::field("status").is("B")))
+            .and(Filter::field("memo").contain("x"));
+        assert_eq!(
+            filter.to_string(),
+            r#"type IS "SalesOrd" AND (status IS "A" OR status IS "B") AND memo CONTAIN "x""#
+        );
+    }
+
+    #[test]
+    fn rejects_non_finite_numbers() {
+        assert!(Filter::field("amount").greater(f64::NAN).to_query().is_err());
+        let nested = Filter::field("type").is("CustInvc").and(Filter::field("amount").less(f64::INFINITY));
+        assert!(matches!(nested.to_query(), Err(NetSuiteError::InvalidRequest(_))));
+        assert_eq!(Filter::field("amount").greater(1.25).to_query().unwrap(), "amount GREATER 1.25");
+    }
+}
@@ -0,0 +1,144 @@
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::time::Duration;
+use tokio::sync::{Semaphore, SemaphorePermit};
+use tokio::time::Instant;
+
+/// Caps the number of requests in flight so that tasks sharing a client never exceed the
+/// account's NetSuite concurrency allowance. Requests beyond the limit wait in FIFO order.
+#[derive(Debug)]
+pub struct ConcurrencyGovernor {
+    limit: usize,
+    semaphore: Semaphore,
+    waiting: AtomicUsize,
+    acquired: AtomicU64,
+    total_wait_micros: AtomicU64,
+    max_wait_micros: AtomicU64,
+}
+
+/// Snapshot of a governor's state
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GovernorMetrics {
+    /// Configured maximum of concurrent requests; 0 means unlimited
+    pub limit: usize,
+    /// Requests currently holding a slot; not tracked when unlimited
+    pub in_flight: usize,
+    /// Requests waiting for a slot
+    pub queue_depth: usize,
+    /// Slots handed out since the client was created
+    pub acquired: u64,
+    /// Sum of the time requests spent queued
+    pub total_wait: Duration,
+    /// Longest time a single request spent queued
+    pub max_wait: Duration,
+}
+
+impl GovernorMetrics {
+    /// Mean time spent queued per request
+    pub fn average_wait(&self) -> Duration {
+        if self.acquired == 0 {
+            Duration::ZERO
+        } else {
+            Duration::from_nanos((self.total_wait.as_nanos() / u128::from(self.acquired)) as u64)
+        }
+    }
+}
+
+/// A concurrency slot, released when dropped
+pub struct GovernorPermit<'a> {
+    _permit: Option<SemaphorePermit<'a>>,
+}
+
+impl ConcurrencyGovernor {
+    /// Allow at most `limit` concurrent requests; 0 disables the limit
+    pub fn new(limit: usize) -> Self {
+        let permits = if limit == 0 { Semaphore::MAX_PERMITS } else { limit };
+        Self {
+            limit,
+            semaphore: Semaphore::new(permits),
+            waiting: AtomicUsize::new(0),
+            acquired: AtomicU64::new(0),
+            total_wait_micros: AtomicU64::new(0),
+            max_wait_micros: AtomicU64::new(0),
+        }
+    }
+
+    /// Wait for a free slot
+    pub async fn acquire(&self) -> GovernorPermit<'_> {
+        if self.limit == 0 {
+            self.acquired.fetch_add(1, Ordering::Relaxed);
+            return GovernorPermit { _permit: None };
+        }
+
+        let started = Instant::now();
+        self.waiting.fetch_add(1, Ordering::Relaxed);
+        // The semaphore is never closed, so acquiring cannot fail
+        let permit = self.semaphore.acquire().await.ok();
+        self.waiting.fetch_sub(1, Ordering::Relaxed);
+
+        let waited = started.elapsed().as_micros() as u64;
+        self.acquired.fetch_add(1, Ordering::Relaxed);
+        self.total_wait_micros.fetch_add(waited, Ordering::Relaxed);
+        self.max_wait_micros.fetch_max(waited, Ordering::Relaxed);
+
+        GovernorPermit { _permit: permit }
+    }
+
+    pub fn metrics(&self) -> GovernorMetrics {
+        let in_flight = if self.limit == 0 {
+            0
+        } else {
+            self.limit - self.semaphore.available_permits()
+        };
+
+        GovernorMetrics {
+            limit: self.limit,
+            in_flight,
+            queue_depth: self.waiting.load(Ordering::Relaxed),
+            acquired: self.acquired.load(Ordering::Relaxed),
+            total_wait: Duration::from_micros(self.total_wait_micros.load(Ordering::Relaxed)),
+            max_wait: Duration::from_micros(self.max_wait_micros.load(Ordering::Relaxed)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::time;
+
+    #[tokio::test(start_paused = true)]
+    async fn requests_beyond_the_limit_queue_until_a_slot_frees() {
+        let governor = ConcurrencyGovernor::new(1);
+        let first = governor.acquire().await;
+
+        let second = governor.acquire();
+        tokio::pin!(second);
+        assert!(futures::poll!(second.as_mut()).is_pending());
+
+        let metrics = governor.metrics();
+        assert_eq!((metrics.in_flight, metrics.queue_depth), (1, 1));
+
+        time::advance(Duration::from_millis(300)).await;
+        assert_eq!(governor.metrics().queue_depth, 1);
+        drop(first);
+
+        let _second = second.await;
+        let metrics = governor.metrics();
+        assert_eq!((metrics.in_flight, metrics.queue_depth, metrics.acquired), (1, 0, 2));
+        assert_eq!(metrics.max_wait, Duration::from_millis(300));
+        assert_eq!(metrics.total_wait, Duration::from_millis(300));
+        assert_eq!(metrics.average_wait(), Duration::from_millis(150));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn unlimited_governor_never_blocks() {
+        let governor = ConcurrencyGovernor::new(0);
+        let permits: Vec<_> = futures::future::join_all((0..100).map(|_| governor.acquire())).await;
+
+        let metrics = governor.metrics();
+        assert_eq!(permits.len(), 100);
+        assert_eq!((metrics.limit, metrics.in_flight, metrics.queue_depth, metrics.acquired), (0, 0, 0, 100));
+        assert_eq!(metrics.max_wait, Duration::ZERO);
+        assert_eq!(metrics.average_wait(), Duration::ZERO);
+    }
+}
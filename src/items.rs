@@ -0,0 +1,428 @@
+use serde::{Deserialize, Serialize, Serializer};
+use std::collections::BTreeMap;
+
+use crate::error::{NetSuiteError, Result};
+use crate::netsuite_client::{EntityReference, Link};
+use crate::record::Record;
+
+/// Sale, resale or purchase flavour of a non-inventory or service item.
+/// NetSuite stores each flavour as its own record type.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ItemSubtype {
+    #[default]
+    Sale,
+    Resale,
+    Purchase,
+}
+
+/// The item record types `Item` covers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ItemType {
+    Inventory,
+    NonInventory(ItemSubtype),
+    Service(ItemSubtype),
+    Kit,
+    Assembly,
+}
+
+impl ItemType {
+    /// REST record type name, e.g. `inventoryitem` or `noninventoryresaleitem`
+    pub fn record_type(&self) -> &'static str {
+        match self {
+            ItemType::Inventory => "inventoryitem",
+            ItemType::NonInventory(ItemSubtype::Sale) => "noninventorysaleitem",
+            ItemType::NonInventory(ItemSubtype::Resale) => "noninventoryresaleitem",
+            ItemType::NonInventory(ItemSubtype::Purchase) => "noninventorypurchaseitem",
+            ItemType::Service(ItemSubtype::Sale) => "servicesaleitem",
+            ItemType::Service(ItemSubtype::Resale) => "serviceresaleitem",
+            ItemType::Service(ItemSubtype::Purchase) => "servicepurchaseitem",
+            ItemType::Kit => "kititem",
+            ItemType::Assembly => "assemblyitem",
+        }
+    }
+
+    /// Map the `itemtype` and `subtype` columns of SuiteQL's `item` table
+    pub(crate) fn from_suiteql(item_type: &str, subtype: Option<&str>) -> Result<Self> {
+        let subtype = match subtype {
+            Some("Resale") => ItemSubtype::Resale,
+            Some("Purchase") => ItemSubtype::Purchase,
+            _ => ItemSubtype::Sale,
+        };
+
+        match item_type {
+            "InvtPart" => Ok(ItemType::Inventory),
+            "NonInvtPart" => Ok(ItemType::NonInventory(subtype)),
+            "Service" => Ok(ItemType::Service(subtype)),
+            "Kit" => Ok(ItemType::Kit),
+            "Assembly" => Ok(ItemType::Assembly),
+            other => Err(NetSuiteError::InvalidRequest(format!("{} items are not supported", other))),
+        }
+    }
+}
+
+/// A catalog item of any supported type.
+///
+/// Non-inventory and service items share one struct across three record types each, so they
+/// cannot implement `Record`. Work with items through `Item` and the client's item methods
+/// instead: `get_item`, `get_item_as`, `list_items`, `create_item`, `update_item`, `upsert_item`
+/// and `delete_item`. `Item` serializes as the record body of its type.
+#[derive(Debug, Clone)]
+pub enum Item {
+    Inventory(InventoryItem),
+    NonInventory(ItemSubtype, NonInventoryItem),
+    Service(ItemSubtype, ServiceItem),
+    Kit(KitItem),
+    Assembly(AssemblyItem),
+}
+
+impl Item {
+    pub fn item_type(&self) -> ItemType {
+        match self {
+            Item::Inventory(_) => ItemType::Inventory,
+            Item::NonInventory(subtype, _) => ItemType::NonInventory(*subtype),
+            Item::Service(subtype, _) => ItemType::Service(*subtype),
+            Item::Kit(_) => ItemType::Kit,
+            Item::Assembly(_) => ItemType::Assembly,
+        }
+    }
+
+    /// Fields every item type has
+    pub fn fields(&self) -> &ItemFields {
+        match self {
+            Item::Inventory(item) => &item.fields,
+            Item::NonInventory(_, item) => &item.fields,
+            Item::Service(_, item) => &item.fields,
+            Item::Kit(item) => &item.fields,
+            Item::Assembly(item) => &item.fields,
+        }
+    }
+
+    pub fn fields_mut(&mut self) -> &mut ItemFields {
+        match self {
+            Item::Inventory(item) => &mut item.fields,
+            Item::NonInventory(_, item) => &mut item.fields,
+            Item::Service(_, item) => &mut item.fields,
+            Item::Kit(item) => &mut item.fields,
+            Item::Assembly(item) => &mut item.fields,
+        }
+    }
+
+    pub fn id(&self) -> Option<&str> {
+        self.fields().id.as_deref()
+    }
+
+    /// Deserialize a record body of the given type
+    pub fn from_json(item_type: ItemType, value: serde_json::Value) -> Result<Self> {
+        Ok(match item_type {
+            ItemType::Inventory => Item::Inventory(serde_json::from_value(value)?),
+            ItemType::NonInventory(subtype) => Item::NonInventory(subtype, serde_json::from_value(value)?),
+            ItemType::Service(subtype) => Item::Service(subtype, serde_json::from_value(value)?),
+            ItemType::Kit => Item::Kit(serde_json::from_value(value)?),
+            ItemType::Assembly => Item::Assembly(serde_json::from_value(value)?),
+        })
+    }
+
+    /// Record body to send on a write
+    pub fn to_json(&self) -> Result<serde_json::Value> {
+        Ok(serde_json::to_value(self)?)
+    }
+}
+
+impl Serialize for Item {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        match self {
+            Item::Inventory(item) => item.serialize(serializer),
+            Item::NonInventory(_, item) => item.serialize(serializer),
+            Item::Service(_, item) => item.serialize(serializer),
+            Item::Kit(item) => item.serialize(serializer),
+            Item::Assembly(item) => item.serialize(serializer),
+        }
+    }
+}
+
+/// Fields shared by all item types, flattened into each type's record body
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ItemFields {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external_id: Option<String>,
+    /// Item name/number, unique across all items
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub item_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upc_code: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sales_description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub purchase_description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub is_inactive: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub class: Option<EntityReference>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub department: Option<EntityReference>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<EntityReference>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tax_schedule: Option<EntityReference>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub income_account: Option<EntityReference>,
+    /// Prices per price level, present when fetched with `expand_sub_resources`
+    #[serde(rename = "price", default, with = "crate::record::sublist", skip_serializing_if = "Vec::is_empty")]
+    pub prices: Vec<ItemPrice>,
+    #[serde(default, skip_serializing)]
+    pub created_date: Option<String>,
+    #[serde(default, skip_serializing)]
+    pub last_modified_date: Option<String>,
+    #[serde(flatten, with = "crate::record::custom_fields")]
+    pub custom_fields: BTreeMap<String, serde_json::Value>,
+    #[serde(default, skip_serializing)]
+    pub links: Vec<Link>,
+}
+
+impl ItemFields {
+    /// Base price (no quantity break) at the price level with internal ID `level_id`
+    pub fn price_at(&self, level_id: &str) -> Option<f64> {
+        self.prices
+            .iter()
+            .filter(|price| price.quantity.unwrap_or_default() == 0.0)
+            .find(|price| price.price_level.as_ref().is_some_and(|level| level.id == level_id))
+            .and_then(|price| price.price)
+    }
+}
+
+/// A line of an item's `price` sublist
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ItemPrice {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub price_level: Option<EntityReference>,
+    /// Only set with multiple currencies enabled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub currency: Option<EntityReference>,
+    /// Lower bound of the quantity break; 0 or unset for the base price
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quantity: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub price: Option<f64>,
+}
+
+impl ItemPrice {
+    /// Base price at the price level with internal ID `level_id`
+    pub fn new(level_id: impl Into<String>, price: f64) -> Self {
+        Self {
+            price_level: Some(EntityReference::new(level_id)),
+            price: Some(price),
+            ..Self::default()
+        }
+    }
+}
+
+/// A line of a kit's or assembly's `member` sublist
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ItemMember {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub item: Option<EntityReference>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quantity: Option<f64>,
+}
+
+impl ItemMember {
+    /// `quantity` of the item with internal ID `item_id`
+    pub fn new(item_id: impl Into<String>, quantity: f64) -> Self {
+        Self {
+            item: Some(EntityReference::new(item_id)),
+            quantity: Some(quantity),
+            ..Self::default()
+        }
+    }
+}
+
+/// `record/v1/inventoryitem`: a stocked item with on-hand quantities
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InventoryItem {
+    /// Purchase price
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub asset_account: Option<EntityReference>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cogs_account: Option<EntityReference>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reorder_point: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preferred_stock_level: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weight: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weight_unit: Option<EntityReference>,
+    #[serde(default, skip_serializing)]
+    pub average_cost: Option<f64>,
+    #[serde(default, skip_serializing)]
+    pub quantity_on_hand: Option<f64>,
+    #[serde(default, skip_serializing)]
+    pub quantity_available: Option<f64>,
+    #[serde(flatten)]
+    pub fields: ItemFields,
+}
+
+/// `record/v1/noninventory{sale,resale,purchase}item`: an item that is not stocked
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NonInventoryItem {
+    /// Purchase price; resale and purchase items only
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost: Option<f64>,
+    /// Resale and purchase items only
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expense_account: Option<EntityReference>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weight: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weight_unit: Option<EntityReference>,
+    #[serde(flatten)]
+    pub fields: ItemFields,
+}
+
+/// `record/v1/service{sale,resale,purchase}item`: labour or another service
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServiceItem {
+    /// Purchase price; resale and purchase items only
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost: Option<f64>,
+    /// Resale and purchase items only
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expense_account: Option<EntityReference>,
+    #[serde(flatten)]
+    pub fields: ItemFields,
+}
+
+/// `record/v1/kititem`: a bundle of member items sold together and never assembled
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KitItem {
+    #[serde(rename = "member", default, with = "crate::record::sublist", skip_serializing_if = "Vec::is_empty")]
+    pub members: Vec<ItemMember>,
+    #[serde(flatten)]
+    pub fields: ItemFields,
+}
+
+/// `record/v1/assemblyitem`: an item built from member components and stocked
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssemblyItem {
+    /// Bill of materials
+    #[serde(rename = "member", default, with = "crate::record::sublist", skip_serializing_if = "Vec::is_empty")]
+    pub members: Vec<ItemMember>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub asset_account: Option<EntityReference>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cogs_account: Option<EntityReference>,
+    #[serde(default, skip_serializing)]
+    pub quantity_on_hand: Option<f64>,
+    #[serde(flatten)]
+    pub fields: ItemFields,
+}
+
+// Non-inventory and service items have no `Record` impl; see `Item`
+impl Record for InventoryItem {
+    const RECORD_TYPE: &'static str = "inventoryitem";
+    type Id = String;
+
+    fn id(&self) -> Option<String> {
+        self.fields.id.clone()
+    }
+}
+
+impl Record for KitItem {
+    const RECORD_TYPE: &'static str = "kititem";
+    type Id = String;
+
+    fn id(&self) -> Option<String> {
+        self.fields.id.clone()
+    }
+}
+
+impl Record for AssemblyItem {
+    const RECORD_TYPE: &'static str = "assemblyitem";
+    type Id = String;
+
+    fn id(&self) -> Option<String> {
+        self.fields.id.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn item_types_map_from_suiteql_columns() {
+        assert_eq!(ItemType::from_suiteql("InvtPart", None).unwrap(), ItemType::Inventory);
+        assert_eq!(ItemType::from_suiteql("NonInvtPart", Some("Sale")).unwrap(), ItemType::NonInventory(ItemSubtype::Sale));
+        assert_eq!(ItemType::from_suiteql("NonInvtPart", Some("Resale")).unwrap(), ItemType::NonInventory(ItemSubtype::Resale));
+        assert_eq!(ItemType::from_suiteql("Service", Some("Purchase")).unwrap(), ItemType::Service(ItemSubtype::Purchase));
+        assert_eq!(ItemType::from_suiteql("Service", None).unwrap(), ItemType::Service(ItemSubtype::Sale));
+        assert_eq!(ItemType::from_suiteql("Kit", None).unwrap(), ItemType::Kit);
+        assert_eq!(ItemType::from_suiteql("Assembly", None).unwrap(), ItemType::Assembly);
+        assert_eq!(ItemType::from_suiteql("Service", Some("Resale")).unwrap().record_type(), "serviceresaleitem");
+
+        let err = ItemType::from_suiteql("GiftCert", None).unwrap_err();
+        assert!(err.to_string().contains("GiftCert"));
+    }
+
+    #[test]
+    fn price_at_picks_the_base_price_of_a_level() {
+        let fields = ItemFields {
+            prices: vec![
+                ItemPrice { quantity: Some(10.0), ..ItemPrice::new("1", 8.5) },
+                ItemPrice::new("1", 10.0),
+                ItemPrice { quantity: Some(0.0), ..ItemPrice::new("2", 9.0) },
+                ItemPrice { price: None, ..ItemPrice::new("3", 0.0) },
+            ],
+            ..ItemFields::default()
+        };
+
+        assert_eq!(fields.price_at("1"), Some(10.0));
+        assert_eq!(fields.price_at("2"), Some(9.0));
+        assert_eq!(fields.price_at("3"), None);
+        assert_eq!(fields.price_at("4"), None);
+    }
+
+    #[test]
+    fn items_serialize_as_their_record_body() {
+        let item = Item::Service(
+            ItemSubtype::Resale,
+            ServiceItem {
+                cost: Some(40.0),
+                fields: ItemFields {
+                    item_id: Some("CONSULT-HR".to_string()),
+                    prices: vec![ItemPrice::new("1", 95.0)],
+                    ..ItemFields::default()
+                },
+                ..ServiceItem::default()
+            },
+        );
+
+        assert_eq!(
+            serde_json::to_value(&item).unwrap(),
+            json!({
+                "cost": 40.0,
+                "itemId": "CONSULT-HR",
+                "price": {"items": [{"priceLevel": {"id": "1"}, "price": 95.0}]}
+            })
+        );
+    }
+}
@@ -0,0 +1,191 @@
+use reqwest::{header::LOCATION, Method, StatusCode};
+use serde::Deserialize;
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+use crate::error::{NetSuiteError, Result};
+use crate::netsuite_client::{expect_success, last_segment, NetSuiteClient};
+
+/// First poll delay; doubled after every poll up to `MAX_POLL_INTERVAL`
+const INITIAL_POLL_INTERVAL: Duration = Duration::from_secs(1);
+const MAX_POLL_INTERVAL: Duration = Duration::from_secs(15);
+
+/// A request NetSuite accepted with `202 Accepted` and is processing in the background.
+///
+/// Returned by `NetSuiteClient::submit_async` and the `*_async` write methods.
+/// Poll it with `status()`, block on it with `wait()`, or abandon it with `cancel()`.
+/// The handle owns a clone of the client, so it can be moved into another task.
+#[derive(Clone)]
+pub struct JobHandle {
+    client: NetSuiteClient,
+    id: String,
+    location: String,
+}
+
+/// Progress of an async job as reported by `async/v1/job/{id}`
+#[derive(Debug, Deserialize)]
+pub struct JobStatus {
+    pub id: String,
+    pub completed: bool,
+    #[serde(default)]
+    pub progress: Option<String>,
+}
+
+/// Final response of the request a job ran
+#[derive(Debug)]
+pub struct JobResult {
+    /// HTTP status the underlying request finished with
+    pub status: StatusCode,
+    /// `Location` of the written record, for creates and upserts
+    pub location: Option<String>,
+    /// Response body, if the request produced one
+    pub body: Option<serde_json::Value>,
+}
+
+impl JobResult {
+    /// Internal ID parsed from the last segment of `location`
+    pub fn record_id(&self) -> Option<&str> {
+        Some(last_segment(self.location.as_deref()?)).filter(|id| !id.is_empty())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct TaskList {
+    #[serde(default)]
+    items: Vec<TaskItem>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TaskItem {
+    id: String,
+}
+
+impl JobHandle {
+    /// Build a handle from the `Location` header of a `202 Accepted` response
+    pub(crate) fn from_location(client: NetSuiteClient, location: &str) -> Result<Self> {
+        let id = Some(last_segment(location))
+            .filter(|id| !id.is_empty())
+            .ok_or_else(|| NetSuiteError::InvalidResponse(format!("Job Location has no ID: {}", location)))?;
+
+        Ok(Self {
+            client,
+            id: id.to_string(),
+            location: location.to_string(),
+        })
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Job URL as returned by NetSuite
+    pub fn location(&self) -> &str {
+        &self.location
+    }
+
+    /// Fetch the current job status
+    pub async fn status(&self) -> Result<JobStatus> {
+        let response = self.client.send_get(&self.job_url("")).await?;
+        let response = expect_success(response, "async job", "poll").await?;
+        Ok(response.json().await?)
+    }
+
+    /// Poll until the job completes and return the response of its request.
+    ///
+    /// Polling backs off from 1s to 15s between requests. Fails if the job has not
+    /// completed within `timeout` or if the underlying request failed.
+    pub async fn wait(&self, timeout: Duration) -> Result<JobResult> {
+        let deadline = Instant::now() + timeout;
+        let mut interval = INITIAL_POLL_INTERVAL;
+
+        loop {
+            let status = self.status().await?;
+            if status.completed {
+                break;
+            }
+
+            let now = Instant::now();
+            if now >= deadline {
+                return Err(NetSuiteError::Timeout(format!("Async job {} did not complete within {:?}", self.id, timeout)));
+            }
+            debug!("Async job {} in progress ({:?}), polling again in {:?}", self.id, status.progress, interval);
+            tokio::time::sleep(interval.min(deadline - now)).await;
+            interval = (interval * 2).min(MAX_POLL_INTERVAL);
+        }
+
+        self.result().await
+    }
+
+    /// Cancel the job; NetSuite only honours this before the job has started running
+    pub async fn cancel(&self) -> Result<()> {
+        let response = self.client.send(Method::DELETE, &self.job_url(""), None, Default::default()).await?;
+        expect_success(response, "async job", "cancel").await?;
+        Ok(())
+    }
+
+    /// Response of the job's task, once the job has completed
+    async fn result(&self) -> Result<JobResult> {
+        let response = self.client.send_get(&self.job_url("/task")).await?;
+        let tasks: TaskList = expect_success(response, "async job", "list tasks of").await?.json().await?;
+        let task = tasks
+            .items
+            .first()
+            .ok_or_else(|| NetSuiteError::InvalidResponse(format!("Async job {} completed without a task", self.id)))?;
+
+        let response = self.client.send_get(&self.job_url(&format!("/task/{}/result", task.id))).await?;
+        let status = response.status();
+        let location = response
+            .headers()
+            .get(LOCATION)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+        let text = response.text().await?;
+
+        if !status.is_success() {
+            return Err(NetSuiteError::from_parts(status, None, &text, format!("run async job {}", self.id)));
+        }
+
+        let body = if text.trim().is_empty() { None } else { Some(serde_json::from_str(&text)?) };
+        Ok(JobResult { status, location, body })
+    }
+
+    fn job_url(&self, suffix: &str) -> String {
+        self.client.rest_url(&format!("async/v1/job/{}{}", self.id, suffix))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{AppConfig, NetSuiteConfig};
+
+    fn client() -> NetSuiteClient {
+        let netsuite = NetSuiteConfig { account_id: "123456".to_string(), ..NetSuiteConfig::default() };
+        NetSuiteClient::new(AppConfig { netsuite }).unwrap()
+    }
+
+    fn result_at(location: Option<&str>) -> JobResult {
+        JobResult { status: StatusCode::NO_CONTENT, location: location.map(str::to_string), body: None }
+    }
+
+    #[test]
+    fn from_location_takes_the_job_id() {
+        let location = "https://123456.suitetalk.api.netsuite.com/services/rest/async/v1/job/7";
+        let handle = JobHandle::from_location(client(), location).unwrap();
+        assert_eq!(handle.id(), "7");
+        assert_eq!(handle.location(), location);
+
+        assert_eq!(JobHandle::from_location(client(), &format!("{}/", location)).unwrap().id(), "7");
+        assert!(JobHandle::from_location(client(), "").is_err());
+        assert!(JobHandle::from_location(client(), "/").is_err());
+    }
+
+    #[test]
+    fn record_id_is_the_last_location_segment() {
+        let location = "https://123456.suitetalk.api.netsuite.com/services/rest/record/v1/customer/1042";
+        assert_eq!(result_at(Some(location)).record_id(), Some("1042"));
+        assert_eq!(result_at(Some(&format!("{}/", location))).record_id(), Some("1042"));
+        assert_eq!(result_at(Some("")).record_id(), None);
+        assert_eq!(result_at(None).record_id(), None);
+    }
+}
@@ -1,7 +1,30 @@
+pub mod auth;
 pub mod config;
+pub mod entities;
+pub mod error;
+pub mod filter;
+pub mod governor;
+pub mod items;
+pub mod job;
 pub mod netsuite_client;
+pub mod rate_limit;
+pub mod record;
+pub mod retry;
+pub mod suiteql;
+pub mod transactions;
+pub mod transform;
 pub mod cli;
 
+pub use auth::Authenticator;
 pub use config::AppConfig;
+pub use entities::Vendor;
+pub use error::{NetSuiteError, Result};
+pub use filter::Filter;
+pub use items::{Item, ItemFields, ItemPrice, ItemSubtype, ItemType};
 pub use netsuite_client::{NetSuiteClient, Customer, CustomerResponse};
+pub use transactions::{
+    BillSettlement, CreditMemo, CustomerApply, CustomerPayment, Invoice, SalesOrder, SalesOrderLine, VendorBill, VendorPayment,
+    VendorPaymentApply,
+};
+pub use record::{Created, FetchOptions, Fields, ListQuery, Record, Sublist, WriteOptions};
 pub use cli::Cli;
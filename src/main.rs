@@ -1,13 +1,8 @@
 use anyhow::Result;
 use tracing::{info, error};
 
-mod netsuite_client;
-mod config;
-mod cli;
-
-use netsuite_client::NetSuiteClient;
-use config::AppConfig;
-use cli::Cli;
+use netsuite_client::{NetSuiteClient, AppConfig, Cli};
+use netsuite_client::auth::AuthorizationCodeProvider;
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -25,12 +20,30 @@ async fn main() -> Result<()> {
         return Ok(());
     }
     
+    // Interactive OAuth 2.0 login (authorization code + PKCE)
+    if args.len() > 1 && args[1] == "login" {
+        run_login().await?;
+        return Ok(());
+    }
+    
     // Run in basic mode
     run_basic_mode().await?;
     
     Ok(())
 }
 
+async fn run_login() -> Result<()> {
+    let config = AppConfig::load()?;
+    let provider = AuthorizationCodeProvider::new(&config.netsuite)?;
+    
+    provider
+        .login_with(|url| println!("Open this URL in your browser to log in to NetSuite:\n\n{}\n", url))
+        .await?;
+    info!("Login successful; set auth_method = \"authorization_code\" to use the stored tokens");
+    
+    Ok(())
+}
+
 async fn run_basic_mode() -> Result<()> {
     info!("Running in basic mode");
     
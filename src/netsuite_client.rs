@@ -1,191 +1,914 @@
-use anyhow::Result;
-use oauth2::{
-    basic::BasicClient, AuthUrl, ClientId, ClientSecret, TokenUrl,
-};
-use reqwest::{Client, header::{HeaderMap, HeaderValue, AUTHORIZATION}};
-use serde::{Deserialize, Serialize};
+use chrono::NaiveDate;
+use futures::{future, stream, Stream, TryStreamExt};
+use reqwest::{Client, Method, Response, StatusCode, Url, header::{HeaderMap, HeaderValue, CONTENT_TYPE, LOCATION}};
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::{debug, warn};
+use uuid::Uuid;
+use crate::auth::{self, AuthRequest, Authenticator};
 use crate::config::NetSuiteConfig;
+use crate::error::{ApiError, ErrorDetail, Result};
+use crate::filter::Filter;
+use crate::governor::{ConcurrencyGovernor, GovernorMetrics};
+use crate::items::{Item, ItemType};
+use crate::job::JobHandle;
+use crate::rate_limit::RateLimiter;
+use crate::record::{Created, FetchOptions, ListQuery, Record, RecordRef, Sublist, WriteOptions};
+use crate::retry::{self, RetryPolicy};
+use crate::suiteql::{col, Order, Query};
+use crate::transactions::{BillSettlement, CustomerApply, CustomerPayment, Invoice, SalesOrder, VendorBill, VendorPayment};
+use crate::transform::{check_transform, TransformInto, Transformed};
+
+pub use crate::error::NetSuiteError;
 
+/// Handle to a NetSuite account.
+///
+/// Cloning is cheap: clones share the connection pool, authenticator and token cache, rate limiter
+/// and concurrency governor, so one client can be cloned into every spawned task. The client is
+/// `Send + Sync`. `with_retry_policy` and `with_rate_limiter` only affect the handle they are called on.
+#[derive(Clone)]
 pub struct NetSuiteClient {
+    inner: Arc<ClientInner>,
+    retry: RetryPolicy,
+    rate_limiter: Option<Arc<RateLimiter>>,
+}
+
+struct ClientInner {
     config: NetSuiteConfig,
     http_client: Client,
-    _oauth_client: BasicClient, // Prefix with _ to indicate intentionally unused
+    auth: Box<dyn Authenticator>,
+    governor: ConcurrencyGovernor,
 }
 
+// Clones are moved into spawned tasks; keep that compiling
+const _: fn() = || {
+    fn assert_send_sync<T: Send + Sync + 'static>() {}
+    assert_send_sync::<NetSuiteClient>();
+};
+
 impl NetSuiteClient {
+    /// Create a client using the authentication scheme selected by `auth_method`
     pub fn new(config: crate::config::AppConfig) -> Result<Self> {
-        let netsuite_config = config.netsuite;
-        
-        let oauth_client = BasicClient::new(
-            ClientId::new(netsuite_config.consumer_key.clone()),
-            Some(ClientSecret::new(netsuite_config.consumer_secret.clone())),
-            AuthUrl::from_url(format!("{}/oauth/authorize", netsuite_config.base_url).parse()?),
-            Some(TokenUrl::from_url(format!("{}/oauth/token", netsuite_config.base_url).parse()?))
-        );
-        
         let http_client = Client::new();
+        let auth = auth::from_config(&config.netsuite, http_client.clone())?;
         
-        Ok(Self {
-            config: netsuite_config,
-            http_client,
-            _oauth_client: oauth_client,
-        })
+        Ok(Self::from_parts(config.netsuite, http_client, auth))
+    }
+    
+    /// Create a client that authenticates every request with a custom `Authenticator`
+    pub fn with_authenticator(config: crate::config::AppConfig, authenticator: impl Authenticator + 'static) -> Result<Self> {
+        Ok(Self::from_parts(config.netsuite, Client::new(), Box::new(authenticator)))
+    }
+    
+    fn from_parts(config: NetSuiteConfig, http_client: Client, auth: Box<dyn Authenticator>) -> Self {
+        Self {
+            retry: config.retry.clone(),
+            rate_limiter: config.rate_limit.as_ref().map(RateLimiter::from_config),
+            inner: Arc::new(ClientInner {
+                governor: ConcurrencyGovernor::new(config.max_concurrent_requests),
+                config,
+                http_client,
+                auth,
+            }),
+        }
+    }
+    
+    /// Queue depth, in-flight count and wait times of the concurrency governor
+    pub fn governor_metrics(&self) -> GovernorMetrics {
+        self.inner.governor.metrics()
+    }
+    
+    /// Draw on the given call budget, replacing `NetSuiteConfig::rate_limit`. Pass the same
+    /// limiter to several clients, or use `RateLimiter::shared`, to make them share one budget.
+    pub fn with_rate_limiter(mut self, limiter: Arc<RateLimiter>) -> Self {
+        self.rate_limiter = Some(limiter);
+        self
+    }
+    
+    /// Replace the retry policy from `NetSuiteConfig::retry`, e.g. `RetryPolicy::none()` to fail fast
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry = policy;
+        self
     }
     
     pub async fn test_connection(&self) -> Result<()> {
         // Simple test to verify we can reach NetSuite
-        let response = self.http_client
-            .get(&format!("{}/rest/platform/v1/record/customer", self.config.base_url))
-            .headers(self.get_auth_headers()?)
-            .send()
-            .await?;
+        let response = self.send_get(&self.rest_url("record/v1/customer")).await?;
         
         if response.status().is_success() || response.status().as_u16() == 401 {
             // 401 is expected without proper OAuth token, but means we can reach the API
             Ok(())
         } else {
-            anyhow::bail!("Failed to connect to NetSuite: {}", response.status())
+            Err(NetSuiteError::from_response(response, "connect to NetSuite").await)
         }
     }
     
     pub async fn get_customers(&self, limit: Option<u32>) -> Result<Vec<Customer>> {
-        let mut url = format!("{}/rest/platform/v1/record/customer", self.config.base_url);
-        if let Some(limit) = limit {
-            url.push_str(&format!("?limit={}", limit));
-        }
+        self.list::<Customer>(&ListQuery { limit, ..ListQuery::default() }).await
+    }
+    
+    /// Fetch vendor payments created from `start_date` through the whole of `end_date`, oldest first,
+    /// with their `apply` lines. SuiteQL finds the matching IDs, then each payment is fetched as
+    /// `get_vendor_payment` does, several at a time within the concurrency limit.
+    pub async fn get_vendor_payments(&self, start_date: NaiveDate, end_date: NaiveDate, limit: Option<u32>) -> Result<Vec<VendorPayment>> {
+        let query = vendor_payments_query(start_date, end_date, limit);
+        let ids = self.query::<IdRow>(&query).await?.into_iter().map(|row| row.id);
+        self.fetch_each(ids, &FetchOptions::new().expand_sub_resources()).await
+    }
+    
+    /// Convenience method for 2024 vendor payments (keeps backward compatibility)
+    pub async fn get_vendor_payments_2024(&self, limit: Option<u32>) -> Result<Vec<VendorPayment>> {
+        let (start_date, end_date) = vendor_payments_2024_range();
+        self.get_vendor_payments(start_date, end_date, limit).await
+    }
+    
+    /// Generic method to fetch transactions with custom filters
+    pub async fn get_transactions_with_filters(&self, filter: Option<&Filter>, limit: Option<u32>) -> Result<Vec<Transaction>> {
+        // Use the correct NetSuite endpoint for transactions
+        // Note: NetSuite doesn't have a generic "transaction" endpoint
+        // You need to specify the specific record type, or query across types with `suiteql`
+        self.list::<Transaction>(&ListQuery { filter: filter.cloned(), limit, ..ListQuery::default() }).await
+    }
+    
+    /// Fetch sales orders with custom filters
+    pub async fn get_sales_orders(&self, filter: Option<&Filter>, limit: Option<u32>) -> Result<Vec<SalesOrder>> {
+        self.list::<SalesOrder>(&ListQuery { filter: filter.cloned(), limit, ..ListQuery::default() }).await
+    }
+    
+    /// Fetch one sales order with its item lines
+    pub async fn get_sales_order(&self, id: &str) -> Result<SalesOrder> {
+        self.get_with::<SalesOrder>(&id.to_string(), &FetchOptions::new().expand_sub_resources()).await
+    }
+    
+    /// Book a sales order, e.g. from a web order, and return its new internal ID.
+    /// Set `external_id` and use `upsert` instead to make re-submitting the same order safe.
+    pub async fn create_sales_order(&self, order: &SalesOrder) -> Result<Created<SalesOrder>> {
+        self.create(order).await
+    }
+    
+    /// Update a sales order (PATCH). Lines with a `line` number change that line, other lines are added;
+    /// pass `WriteOptions::new().replace_sublist("item")` to `update_with` to replace all lines instead.
+    /// `changes` may be a fetched order: of its unmodeled fields, only custom ones are sent.
+    pub async fn update_sales_order(&self, id: &str, changes: &SalesOrder) -> Result<()> {
+        self.update::<SalesOrder>(&id.to_string(), changes).await
+    }
+    
+    /// Fetch one vendor payment with its `apply` lines
+    pub async fn get_vendor_payment(&self, id: &str) -> Result<VendorPayment> {
+        self.get_with::<VendorPayment>(&id.to_string(), &FetchOptions::new().expand_sub_resources()).await
+    }
+    
+    /// The bills `payment` settled, each fetched alongside the `apply` line that paid it.
+    /// `payment` must carry its `apply` lines, e.g. from `get_vendor_payment`.
+    pub async fn get_settled_bills(&self, payment: &VendorPayment) -> Result<Vec<(BillSettlement, VendorBill)>> {
+        future::try_join_all(payment.settlements().into_iter().map(|settlement| async move {
+            let bill = self.get::<VendorBill>(&settlement.bill_id).await?;
+            Ok((settlement, bill))
+        }))
+        .await
+    }
+    
+    /// Fetch one invoice with its item lines
+    pub async fn get_invoice(&self, id: &str) -> Result<Invoice> {
+        self.get_with::<Invoice>(&id.to_string(), &FetchOptions::new().expand_sub_resources()).await
+    }
+    
+    /// Every invoice of the customer with internal ID `customer_id` that still has an open balance,
+    /// oldest first, with its item lines. SuiteQL finds the invoices with an unpaid amount, then each
+    /// is fetched as `get_invoice` does, several at a time within the concurrency limit.
+    pub async fn get_open_invoices(&self, customer_id: &str) -> Result<Vec<Invoice>> {
+        let ids = self.query::<IdRow>(&open_invoices_query(customer_id)).await?.into_iter().map(|row| row.id);
+        self.fetch_each(ids, &FetchOptions::new().expand_sub_resources()).await
+    }
+    
+    /// Record a customer payment, e.g. one built with `CustomerPayment::new(..).apply_to(..)`
+    pub async fn create_customer_payment(&self, payment: &CustomerPayment) -> Result<Created<CustomerPayment>> {
+        self.create(payment).await
+    }
+    
+    /// Apply an existing payment's unapplied amount to specific invoices, given as
+    /// `(invoice_id, amount)` pairs. Lines already applied to other invoices are left as they are.
+    pub async fn apply_customer_payment(&self, payment_id: &str, applications: &[(&str, f64)]) -> Result<()> {
+        let changes = CustomerPayment {
+            apply: applications
+                .iter()
+                .map(|&(invoice_id, amount)| CustomerApply::new(invoice_id, amount))
+                .collect(),
+            ..CustomerPayment::default()
+        };
+        self.update::<CustomerPayment>(&payment_id.to_string(), &changes).await
+    }
+    
+    /// Fetch one item with its price and member sublists. Its record type is unknown up front, so
+    /// this costs an extra SuiteQL lookup; use `get_item_as` when the type is known.
+    pub async fn get_item(&self, id: &str) -> Result<Item> {
+        let query = Query::select(["itemtype", "subtype"]).from("item").where_(col("id").eq(id));
+        let row = self
+            .query::<ItemTypeRow>(&query)
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| item_not_found(id))?;
         
-        let response = self.http_client
-            .get(&url)
-            .headers(self.get_auth_headers()?)
-            .send()
-            .await?;
+        self.get_item_as(ItemType::from_suiteql(&row.itemtype, row.subtype.as_deref())?, id).await
+    }
+    
+    /// Fetch one item of a known type with its price and member sublists
+    pub async fn get_item_as(&self, item_type: ItemType, id: &str) -> Result<Item> {
+        let record_type = item_type.record_type();
+        let params = FetchOptions::new().expand_sub_resources().query_params(&[]);
+        let body = self.fetch_record(&record_path(record_type, &id), record_type, params).await?;
+        Item::from_json(item_type, body)
+    }
+    
+    /// Fetch one page of items of one type
+    pub async fn list_items(&self, item_type: ItemType, query: &ListQuery) -> Result<Vec<Item>> {
+        self.list_as::<serde_json::Value>(item_type.record_type(), &[], query)
+            .await?
+            .into_iter()
+            .map(|body| Item::from_json(item_type, body))
+            .collect()
+    }
+    
+    /// Create an item of the type `item` holds (POST) and return its new internal ID
+    pub async fn create_item(&self, item: &Item) -> Result<String> {
+        self.create_item_with(item, &WriteOptions::default()).await
+    }
+    
+    /// `create_item` with explicit write options, e.g. a caller-supplied idempotency key
+    pub async fn create_item_with(&self, item: &Item, options: &WriteOptions) -> Result<String> {
+        let record_type = item.item_type().record_type();
+        let path = format!("record/v1/{}", record_type);
+        let response = self.write(Method::POST, &path, Some(serde_json::to_value(item)?), options, record_type, "create").await?;
+        let location = location_of(&response, record_type)?;
+        Ok(last_segment(&location).to_string())
+    }
+    
+    /// Update the item with internal ID `id` (PATCH) with the fields set on `item`, which may be
+    /// one fetched with `get_item` and modified
+    pub async fn update_item(&self, id: &str, item: &Item) -> Result<()> {
+        self.update_item_with(id, item, &WriteOptions::default()).await
+    }
+    
+    /// `update_item` with explicit write options, e.g. `WriteOptions::replace_sublist("price")`
+    pub async fn update_item_with(&self, id: &str, item: &Item, options: &WriteOptions) -> Result<()> {
+        let record_type = item.item_type().record_type();
+        self.write(Method::PATCH, &record_path(record_type, &id), Some(serde_json::to_value(item)?), options, record_type, "update").await?;
+        Ok(())
+    }
+    
+    /// Delete the item of type `item_type` with internal ID `id`
+    pub async fn delete_item(&self, item_type: ItemType, id: &str) -> Result<()> {
+        self.delete_item_with(item_type, id, &WriteOptions::default()).await
+    }
+    
+    /// `delete_item` with explicit write options
+    pub async fn delete_item_with(&self, item_type: ItemType, id: &str, options: &WriteOptions) -> Result<()> {
+        let record_type = item_type.record_type();
+        self.write(Method::DELETE, &record_path(record_type, &id), None, options, record_type, "delete").await?;
+        Ok(())
+    }
+    
+    /// Create or update an item keyed by our own external ID, e.g. a SKU from the product catalog,
+    /// and return its internal ID. Prices and members sent replace only the lines they match;
+    /// use `upsert_item_with` and `WriteOptions::replace_sublist("price")` to replace all of them.
+    pub async fn upsert_item(&self, external_id: &str, item: &Item) -> Result<String> {
+        self.upsert_item_with(external_id, item, &WriteOptions::default()).await
+    }
+    
+    /// `upsert_item` with explicit write options
+    pub async fn upsert_item_with(&self, external_id: &str, item: &Item, options: &WriteOptions) -> Result<String> {
+        let record_type = item.item_type().record_type();
+        let path = external_id_path(record_type, external_id);
+        let response = self.write(Method::PUT, &path, Some(serde_json::to_value(item)?), options, record_type, "upsert").await?;
+        let location = location_of(&response, record_type)?;
+        Ok(last_segment(&location).to_string())
+    }
+    
+    /// Stream all customers, fetching `page_size` records per request as the stream is polled
+    pub fn stream_customers(&self, page_size: u32) -> impl Stream<Item = Result<Customer>> + '_ {
+        self.stream::<Customer>(None, page_size)
+    }
+    
+    /// Stream vendor payments created between `start_date` and `end_date` with their `apply` lines,
+    /// one page at a time
+    pub fn stream_vendor_payments(&self, start_date: NaiveDate, end_date: NaiveDate, page_size: u32) -> impl Stream<Item = Result<VendorPayment>> + '_ {
+        let fetch = FetchOptions::new().expand_sub_resources();
+        self.stream_with::<VendorPayment>(Some(vendor_payment_filter(start_date, end_date)), page_size, &fetch)
+    }
+    
+    /// Stream transactions matching `filter`, one page at a time
+    pub fn stream_transactions_with_filters(&self, filter: Option<Filter>, page_size: u32) -> impl Stream<Item = Result<Transaction>> + '_ {
+        self.stream::<Transaction>(filter, page_size)
+    }
+    
+    /// Stream sales orders matching `filter`, one page at a time
+    pub fn stream_sales_orders(&self, filter: Option<Filter>, page_size: u32) -> impl Stream<Item = Result<SalesOrder>> + '_ {
+        self.stream::<SalesOrder>(filter, page_size)
+    }
+    
+    /// Fetch a single record by internal ID, requesting only the fields the model declares
+    pub async fn get<R: Record>(&self, id: &R::Id) -> Result<R> {
+        self.get_with(id, &FetchOptions::default()).await
+    }
+    
+    /// `get` with explicit field selection and sub-resource expansion
+    pub async fn get_with<R: Record>(&self, id: &R::Id, fetch: &FetchOptions) -> Result<R> {
+        self.fetch_record(&record_path(R::RECORD_TYPE, id), R::RECORD_TYPE, fetch.query_params(R::FIELDS)).await
+    }
+    
+    /// Fetch one page of records: one request for the page of IDs, then one per record
+    pub async fn list<R: Record>(&self, query: &ListQuery) -> Result<Vec<R>> {
+        self.list_as(R::RECORD_TYPE, R::FIELDS, query).await
+    }
+    
+    /// Lazily stream every record matching `filter`. The collection is read `page_size` IDs per request
+    /// and each record is then fetched on its own, several at a time within the concurrency limit.
+    pub fn stream<R: Record>(&self, filter: Option<Filter>, page_size: u32) -> impl Stream<Item = Result<R>> + '_ {
+        self.stream_with(filter, page_size, &FetchOptions::default())
+    }
+    
+    /// `stream` with explicit field selection and sub-resource expansion
+    pub fn stream_with<R: Record>(&self, filter: Option<Filter>, page_size: u32, fetch: &FetchOptions) -> impl Stream<Item = Result<R>> + '_ {
+        self.paginate(R::RECORD_TYPE, fetch.query_params(R::FIELDS), filter, page_size)
+    }
+    
+    /// Create a record (POST) and return its new internal ID
+    pub async fn create<R: Record>(&self, record: &R) -> Result<Created<R>> {
+        self.create_with(record, &WriteOptions::default()).await
+    }
+    
+    /// `create` with explicit write options, e.g. a caller-supplied idempotency key
+    pub async fn create_with<R: Record>(&self, record: &R, options: &WriteOptions) -> Result<Created<R>> {
+        let path = format!("record/v1/{}", R::RECORD_TYPE);
+        let body = serde_json::to_value(record)?;
+        let response = self.write(Method::POST, &path, Some(body), options, R::RECORD_TYPE, "create").await?;
+        created_from_location(&response)
+    }
+    
+    /// Create a record, then fetch it back so server-populated fields are available in `Created::record`
+    pub async fn create_and_fetch<R: Record>(&self, record: &R) -> Result<Created<R>> {
+        let mut created = self.create(record).await?;
+        created.record = Some(self.get::<R>(&created.id).await?);
+        Ok(created)
+    }
+    
+    /// Create or update the record keyed by our own external ID (PUT `eid:{externalId}`),
+    /// so repeated loads of the same data are idempotent
+    pub async fn upsert<R: Record>(&self, external_id: &str, record: &R) -> Result<Created<R>> {
+        self.upsert_with(external_id, record, &WriteOptions::default()).await
+    }
+    
+    /// `upsert` with explicit write options
+    pub async fn upsert_with<R: Record>(&self, external_id: &str, record: &R, options: &WriteOptions) -> Result<Created<R>> {
+        let path = external_id_path(R::RECORD_TYPE, external_id);
+        let body = serde_json::to_value(record)?;
+        let response = self.write(Method::PUT, &path, Some(body), options, R::RECORD_TYPE, "upsert").await?;
+        created_from_location(&response)
+    }
+    
+    /// Fetch a single record by external ID
+    pub async fn get_by_external_id<R: Record>(&self, external_id: &str) -> Result<R> {
+        self.get_by_external_id_with(external_id, &FetchOptions::default()).await
+    }
+    
+    /// `get_by_external_id` with explicit field selection and sub-resource expansion
+    pub async fn get_by_external_id_with<R: Record>(&self, external_id: &str, fetch: &FetchOptions) -> Result<R> {
+        self.fetch_record(&external_id_path(R::RECORD_TYPE, external_id), R::RECORD_TYPE, fetch.query_params(R::FIELDS)).await
+    }
+    
+    /// Create a customer and return its new internal ID
+    pub async fn create_customer(&self, customer: &Customer) -> Result<Created<Customer>> {
+        self.create(customer).await
+    }
+    
+    /// Update the given fields of a record (PATCH); `changes` may be a full model or any partial JSON
+    pub async fn update<R: Record>(&self, id: &R::Id, changes: &impl Serialize) -> Result<()> {
+        self.update_with::<R>(id, changes, &WriteOptions::default()).await
+    }
+    
+    /// `update` with explicit write options
+    pub async fn update_with<R: Record>(&self, id: &R::Id, changes: &impl Serialize, options: &WriteOptions) -> Result<()> {
+        let body = serde_json::to_value(changes)?;
+        self.write(Method::PATCH, &record_path(R::RECORD_TYPE, id), Some(body), options, R::RECORD_TYPE, "update").await?;
+        Ok(())
+    }
+    
+    /// Replace a record wholesale (PUT)
+    pub async fn replace<R: Record>(&self, id: &R::Id, record: &R) -> Result<()> {
+        self.replace_with(id, record, &WriteOptions::default()).await
+    }
+    
+    /// `replace` with explicit write options
+    pub async fn replace_with<R: Record>(&self, id: &R::Id, record: &R, options: &WriteOptions) -> Result<()> {
+        let body = serde_json::to_value(record)?;
+        self.write(Method::PUT, &record_path(R::RECORD_TYPE, id), Some(body), options, R::RECORD_TYPE, "replace").await?;
+        Ok(())
+    }
+    
+    /// Delete a record
+    pub async fn delete<R: Record>(&self, id: &R::Id) -> Result<()> {
+        self.delete_with::<R>(id, &WriteOptions::default()).await
+    }
+    
+    /// `delete` with explicit write options
+    pub async fn delete_with<R: Record>(&self, id: &R::Id, options: &WriteOptions) -> Result<()> {
+        self.write(Method::DELETE, &record_path(R::RECORD_TYPE, id), None, options, R::RECORD_TYPE, "delete").await?;
+        Ok(())
+    }
+    
+    /// Transform a record into another type (POST `record/v1/{from}/{id}/!transform/{to}`), e.g. a sales order
+    /// into an invoice. `overrides` is sent as the body to set fields on the new record. Pairs not listed in
+    /// `transform::TRANSFORMS` are rejected before anything is sent.
+    pub async fn transform(&self, from_type: &str, id: &str, to_type: &str, overrides: Option<&serde_json::Value>) -> Result<Transformed> {
+        self.transform_with(from_type, id, to_type, overrides, &WriteOptions::default()).await
+    }
+    
+    /// `transform` with explicit write options, e.g. a caller-supplied idempotency key
+    pub async fn transform_with(&self, from_type: &str, id: &str, to_type: &str, overrides: Option<&serde_json::Value>, options: &WriteOptions) -> Result<Transformed> {
+        check_transform(from_type, to_type)?;
+        let path = transform_path(from_type, id, to_type);
+        let body = overrides.cloned().unwrap_or_else(|| serde_json::json!({}));
+        let response = self.write(Method::POST, &path, Some(body), options, from_type, "transform").await?;
         
-        if response.status().is_success() {
-            let customers: CustomerResponse = response.json().await?;
-            Ok(customers.records)
-        } else {
-            anyhow::bail!("Failed to fetch customers: {}", response.status())
+        let location = location_of(&response, to_type)?;
+        let id = last_segment(&location).to_string();
+        Ok(Transformed { record_type: to_type.to_string(), id, location })
+    }
+    
+    /// Typed `transform`: only pairs with a `TransformInto` impl compile
+    pub async fn transform_into<From, To>(&self, id: &From::Id, overrides: Option<&serde_json::Value>) -> Result<Created<To>>
+    where
+        From: TransformInto<To>,
+        To: Record,
+    {
+        self.transform_into_with::<From, To>(id, overrides, &WriteOptions::default()).await
+    }
+    
+    /// `transform_into` with explicit write options
+    pub async fn transform_into_with<From, To>(&self, id: &From::Id, overrides: Option<&serde_json::Value>, options: &WriteOptions) -> Result<Created<To>>
+    where
+        From: TransformInto<To>,
+        To: Record,
+    {
+        let transformed = self.transform_with(From::RECORD_TYPE, &id.to_string(), To::RECORD_TYPE, overrides, options).await?;
+        let id = transformed
+            .id
+            .parse()
+            .map_err(|_| NetSuiteError::InvalidResponse(format!("Unexpected {} ID in Location header: {}", To::RECORD_TYPE, transformed.location)))?;
+        Ok(Created { id, location: transformed.location, record: None })
+    }
+    
+    /// Send a write carrying an `X-NetSuite-Idempotency-Key`. The key is fixed before sending, once per
+    /// logical operation, so every retry of this request presents the same key to NetSuite.
+    async fn write(&self, method: Method, path: &str, body: Option<serde_json::Value>, options: &WriteOptions, record_type: &str, action: &str) -> Result<Response> {
+        let headers = write_headers(options)?;
+        let url = self.rest_url(&write_path(path, options));
+        debug!("{} {} {}", action, record_type, url);
+        expect_success(self.send(method, &url, body.as_ref(), headers).await?, record_type, action).await
+    }
+    
+    /// Submit a create as an asynchronous job (`Prefer: respond-async`)
+    pub async fn create_async<R: Record>(&self, record: &R, options: &WriteOptions) -> Result<JobHandle> {
+        let path = format!("record/v1/{}", R::RECORD_TYPE);
+        let body = serde_json::to_value(record)?;
+        self.submit_async(Method::POST, &write_path(&path, options), Some(body), write_headers(options)?).await
+    }
+    
+    /// Submit a PATCH update as an asynchronous job
+    pub async fn update_async<R: Record>(&self, id: &R::Id, changes: &impl Serialize, options: &WriteOptions) -> Result<JobHandle> {
+        let body = serde_json::to_value(changes)?;
+        self.submit_async(Method::PATCH, &write_path(&record_path(R::RECORD_TYPE, id), options), Some(body), write_headers(options)?).await
+    }
+    
+    /// Submit an external-ID upsert as an asynchronous job
+    pub async fn upsert_async<R: Record>(&self, external_id: &str, record: &R, options: &WriteOptions) -> Result<JobHandle> {
+        let body = serde_json::to_value(record)?;
+        self.submit_async(Method::PUT, &write_path(&external_id_path(R::RECORD_TYPE, external_id), options), Some(body), write_headers(options)?).await
+    }
+    
+    /// Submit a delete as an asynchronous job
+    pub async fn delete_async<R: Record>(&self, id: &R::Id, options: &WriteOptions) -> Result<JobHandle> {
+        self.submit_async(Method::DELETE, &record_path(R::RECORD_TYPE, id), None, write_headers(options)?).await
+    }
+    
+    /// Send any REST request with `Prefer: respond-async`; NetSuite answers `202 Accepted`
+    /// with the job URL in `Location`, which the returned handle polls
+    pub async fn submit_async(&self, method: Method, path: &str, body: Option<serde_json::Value>, mut headers: HeaderMap) -> Result<JobHandle> {
+        headers.insert("Prefer", HeaderValue::from_static("respond-async"));
+        
+        let url = self.rest_url(path);
+        let response = self.send(method.clone(), &url, body.as_ref(), headers).await?;
+        if response.status() != StatusCode::ACCEPTED {
+            let context = format!("submit {} {} as an async job", method, path);
+            return Err(NetSuiteError::from_response(response, context).await);
         }
+        
+        let location = response
+            .headers()
+            .get(LOCATION)
+            .and_then(|value| value.to_str().ok())
+            .ok_or_else(|| NetSuiteError::InvalidResponse(format!("NetSuite returned no job Location for {} {}", method, path)))?;
+        
+        JobHandle::from_location(self.clone(), location)
     }
     
-    /// Fetch vendor payment transactions for specific date ranges
-    /// This implements your SQL-like query: SELECT * FROM Transaction WHERE Type='VendPymt' AND (createddate BETWEEN start_date AND end_date)
-    pub async fn get_vendor_payments(&self, start_date: &str, end_date: &str, limit: Option<u32>) -> Result<Vec<Transaction>> {
-        // Use the correct NetSuite endpoint for vendor payments (checks)
-        let mut url = format!("{}/rest/platform/v1/record/check", self.config.base_url);
+    /// GET a single record with the given `fields=` / `expandSubResources=` parameters
+    async fn fetch_record<T: DeserializeOwned>(&self, path: &str, record_type: &str, params: Vec<String>) -> Result<T> {
+        let url = self.url_with_params(path, params);
+        let response = expect_success(self.send_get(&url).await?, record_type, "fetch").await?;
+        Ok(response.json().await?)
+    }
+    
+    /// Fetch one page of `record_type`, deserialized as `T`, which declares `fields`.
+    /// The page only lists IDs, so each record is then fetched as `paginate` does.
+    async fn list_as<T: DeserializeOwned>(&self, record_type: &str, fields: &[&str], query: &ListQuery) -> Result<Vec<T>> {
+        let url = self.collection_url(&format!("record/v1/{}", record_type), query.filter.as_ref(), query.limit, query.offset, Vec::new())?;
+        let response = expect_success(self.send_get(&url).await?, record_type, "list").await?;
+        let page: Page<RecordRef> = response.json().await?;
         
-        // Build query parameters
-        let mut params = Vec::new();
-        params.push("q=type IS VendPymt".to_string());
-        params.push(format!("q=createddate BETWEEN '{}' AND '{}'", start_date, end_date));
+        self.hydrate(record_type, query.fetch.query_params(fields), stream::iter(page.items.into_iter().map(Ok)))
+            .try_collect()
+            .await
+    }
+    
+    /// Fetch the records with internal IDs `ids`, in order, e.g. IDs found with SuiteQL
+    async fn fetch_each<R: Record>(&self, ids: impl IntoIterator<Item = String>, fetch: &FetchOptions) -> Result<Vec<R>> {
+        let references = ids.into_iter().map(|id| Ok(RecordRef { id, links: Vec::new() }));
         
-        if let Some(limit) = limit {
-            params.push(format!("limit={}", limit));
-        }
+        self.hydrate(R::RECORD_TYPE, fetch.query_params(R::FIELDS), stream::iter(references))
+            .try_collect()
+            .await
+    }
+    
+    /// Lazily walk a record collection with `limit`/`offset` until `hasMore` is false.
+    /// Collection pages only carry `{id, links}` per record, whatever `fields=` or `expandSubResources`
+    /// say, so each record is then fetched with `fetch_params`, several at a time (see `hydrate`).
+    /// Only the current page is held in memory.
+    fn paginate<T: DeserializeOwned + 'static>(&self, record_type: &'static str, fetch_params: Vec<String>, filter: Option<Filter>, page_size: u32) -> impl Stream<Item = Result<T>> + '_ {
+        let page_size = page_size.clamp(1, MAX_PAGE_SIZE);
         
-        // Join all parameters
-        url.push_str(&format!("?{}", params.join("&")));
+        let references = stream::try_unfold(Some(0u64), move |offset| {
+            let filter = filter.clone();
+            async move {
+                let Some(offset) = offset else {
+                    return Ok::<_, NetSuiteError>(None);
+                };
+                
+                let url = self.collection_url(&format!("record/v1/{}", record_type), filter.as_ref(), Some(page_size), Some(offset), Vec::new())?;
+                let response = expect_success(self.send_get(&url).await?, record_type, "list").await?;
+                
+                let page: Page<RecordRef> = response.json().await?;
+                let fetched = page.items.len() as u64;
+                let next = (page.has_more && fetched > 0).then_some(offset + fetched);
+                
+                Ok(Some((page.items, next)))
+            }
+        })
+        .map_ok(|items| stream::iter(items.into_iter().map(Ok)))
+        .try_flatten();
         
-        let response = self.http_client
-            .get(&url)
-            .headers(self.get_auth_headers()?)
-            .send()
-            .await?;
+        self.hydrate(record_type, fetch_params, references)
+    }
+    
+    /// Fetch the record behind each collection reference, in order. Up to the governor's limit of
+    /// fetches run at once (`UNLIMITED_FETCH_CONCURRENCY` when the governor is unlimited).
+    fn hydrate<'a, T: DeserializeOwned + 'a>(&'a self, record_type: &'a str, fetch_params: Vec<String>, references: impl Stream<Item = Result<RecordRef>> + 'a) -> impl Stream<Item = Result<T>> + 'a {
+        let concurrency = match self.inner.governor.metrics().limit {
+            0 => UNLIMITED_FETCH_CONCURRENCY,
+            limit => limit,
+        };
         
-        if response.status().is_success() {
-            let transactions: TransactionResponse = response.json().await?;
-            Ok(transactions.records)
-        } else {
-            anyhow::bail!("Failed to fetch vendor payments: {}", response.status())
-        }
+        references
+            .map_ok(move |reference| {
+                let path = record_path(record_type, &reference.id);
+                let params = fetch_params.clone();
+                async move { self.fetch_record::<T>(&path, record_type, params).await }
+            })
+            .try_buffered(concurrency)
     }
     
-    /// Convenience method for 2024 vendor payments (keeps backward compatibility)
-    pub async fn get_vendor_payments_2024(&self, limit: Option<u32>) -> Result<Vec<Transaction>> {
-        self.get_vendor_payments("2024-05-01", "2024-08-31", limit).await
+    /// Run a SuiteQL query and return every row, following NetSuite's pages until `hasMore` is false
+    /// e.g. `client.suiteql::<MyRow>("SELECT id, tranid FROM transaction WHERE type = 'VendPymt'")`
+    pub async fn suiteql<T: DeserializeOwned>(&self, query: &str) -> Result<Vec<T>> {
+        self.suiteql_rows(query, None).await
     }
     
-    /// Generic method to fetch transactions with custom filters
-    pub async fn get_transactions_with_filters(&self, filters: &[String], limit: Option<u32>) -> Result<Vec<Transaction>> {
-        // Use the correct NetSuite endpoint for transactions
-        // Note: NetSuite doesn't have a generic "transaction" endpoint
-        // You need to specify the specific record type
-        let mut url = format!("{}/rest/platform/v1/record/check", self.config.base_url);
+    /// Run a `Query` built with the SuiteQL builder, honoring its row limit
+    pub async fn query<T: DeserializeOwned>(&self, query: &Query) -> Result<Vec<T>> {
+        self.suiteql_rows(&query.to_sql()?, query.row_limit()).await
+    }
+    
+    async fn suiteql_rows<T: DeserializeOwned>(&self, query: &str, max_rows: Option<u64>) -> Result<Vec<T>> {
+        let body = serde_json::json!({ "q": query });
+        let page_size = max_rows.map_or(SUITEQL_PAGE_SIZE, |max| max.clamp(1, SUITEQL_PAGE_SIZE.into()) as u32);
+        let mut headers = HeaderMap::new();
+        headers.insert("Prefer", HeaderValue::from_static("transient"));
+        
+        let mut rows = Vec::new();
+        let mut next_url = format!("{}?limit={}&offset=0", self.rest_url("query/v1/suiteql"), page_size);
         
-        // Build query parameters from filters
-        let mut params = Vec::new();
-        for filter in filters {
-            params.push(format!("q={}", filter));
+        loop {
+            // Queries are read-only, so they are retried like GETs despite being POSTs
+            let response = self.send_retrying(Method::POST, &next_url, Some(&body), headers.clone(), true).await?;
+            let response = expect_success(response, "query", "run SuiteQL").await?;
+            
+            let page: Page<T> = response.json().await?;
+            let fetched = page.items.len() as u64;
+            rows.extend(page.items);
+            
+            if let Some(max_rows) = max_rows {
+                if rows.len() as u64 >= max_rows {
+                    rows.truncate(max_rows as usize);
+                    break;
+                }
+            }
+            
+            if !page.has_more || fetched == 0 {
+                break;
+            }
+            
+            // Prefer the server's own "next" link; fall back to advancing the offset
+            next_url = match page.links.into_iter().find(|link| link.rel == "next") {
+                Some(link) => link.href,
+                None => format!(
+                    "{}?limit={}&offset={}",
+                    self.rest_url("query/v1/suiteql"),
+                    page_size,
+                    page.offset + fetched
+                ),
+            };
         }
         
+        Ok(rows)
+    }
+    
+    /// Absolute URL for a path under `/services/rest`, e.g. `record/v1/customer`
+    pub(crate) fn rest_url(&self, path: &str) -> String {
+        format!("{}/{}", self.inner.config.rest_url(), path.trim_start_matches('/'))
+    }
+    
+    /// Collection URL with a single percent-encoded `q` expression plus paging and fetch parameters
+    fn collection_url(&self, path: &str, filter: Option<&Filter>, limit: Option<u32>, offset: Option<u64>, fetch_params: Vec<String>) -> Result<String> {
+        let mut params = fetch_params;
+        if let Some(filter) = filter {
+            params.push(format!("q={}", utf8_percent_encode(&filter.to_query()?, URL_ENCODE_SET)));
+        }
         if let Some(limit) = limit {
             params.push(format!("limit={}", limit));
         }
+        if let Some(offset) = offset {
+            params.push(format!("offset={}", offset));
+        }
         
-        // Join all parameters
+        Ok(self.url_with_params(path, params))
+    }
+    
+    fn url_with_params(&self, path: &str, params: Vec<String>) -> String {
+        let mut url = self.rest_url(path);
         if !params.is_empty() {
             url.push_str(&format!("?{}", params.join("&")));
         }
+        url
+    }
+    
+    pub(crate) async fn send_get(&self, url: &str) -> Result<Response> {
+        self.send(Method::GET, url, None, HeaderMap::new()).await
+    }
+    
+    /// Send a request under the retry policy. Writes carrying an idempotency key count as idempotent.
+    pub(crate) async fn send(&self, method: Method, url: &str, body: Option<&serde_json::Value>, headers: HeaderMap) -> Result<Response> {
+        let idempotent = headers.contains_key(IDEMPOTENCY_KEY_HEADER);
+        self.send_retrying(method, url, body, headers, idempotent).await
+    }
+    
+    /// Sign and send a request; the URL is parsed first so the signature
+    /// covers exactly the query string reqwest puts on the wire.
+    /// Throttled and transiently failed attempts are repeated as `RetryPolicy` allows;
+    /// the final response or error is returned as is.
+    async fn send_retrying(&self, method: Method, url: &str, body: Option<&serde_json::Value>, headers: HeaderMap, idempotent: bool) -> Result<Response> {
+        let url = Url::parse(url)?;
+        let body = body.map(serde_json::to_vec).transpose()?;
+        let policy = &self.retry;
+        let retry_allowed = policy.allows(&method, idempotent);
         
-        let response = self.http_client
-            .get(&url)
-            .headers(self.get_auth_headers()?)
-            .send()
-            .await?;
-        
-        if response.status().is_success() {
-            let transactions: TransactionResponse = response.json().await?;
-            Ok(transactions.records)
-        } else {
-            anyhow::bail!("Failed to fetch transactions: {}", response.status())
+        let mut attempt = 1;
+        loop {
+            let result = self.send_authenticated(&method, &url, body.as_deref(), &headers).await;
+            let (reason, retry_after) = match &result {
+                Ok(response) if retry::is_retryable_status(response.status()) => {
+                    (response.status().to_string(), retry::retry_after(response.headers()))
+                }
+                Err(NetSuiteError::Http(e)) if e.is_connect() || e.is_timeout() => (e.to_string(), None),
+                _ => return result,
+            };
+            if !retry_allowed || attempt >= policy.max_attempts {
+                return result;
+            }
+            
+            let delay = policy.delay(attempt, retry_after);
+            warn!(
+                "{} {} failed with {} (attempt {}/{}), retrying in {:?}",
+                method, url, reason, attempt, policy.max_attempts, delay
+            );
+            tokio::time::sleep(delay).await;
+            attempt += 1;
         }
     }
     
-    /// Fetch sales orders with custom filters
-    pub async fn get_sales_orders(&self, filters: &[String], limit: Option<u32>) -> Result<Vec<Transaction>> {
-        let mut url = format!("{}/rest/platform/v1/record/salesorder", self.config.base_url);
-        
-        // Build query parameters from filters
-        let mut params = Vec::new();
-        for filter in filters {
-            params.push(format!("q={}", filter));
+    /// A 401 is retried once if the auth scheme can obtain a fresh token
+    async fn send_authenticated(&self, method: &Method, url: &Url, body: Option<&[u8]>, headers: &HeaderMap) -> Result<Response> {
+        let response = self.send_once(method, url, body, headers).await?;
+        if response.status() != StatusCode::UNAUTHORIZED || !self.inner.auth.handle_unauthorized().await? {
+            return Ok(response);
         }
         
-        if let Some(limit) = limit {
-            params.push(format!("limit={}", limit));
+        debug!("Retrying {} {} with renewed credentials", method, url);
+        self.send_once(method, url, body, headers).await
+    }
+    
+    /// Send a single attempt: take a rate limit token, then hold a governor slot until NetSuite's
+    /// response has been read in full. The body is buffered so the slot is not released while a
+    /// large page is still streaming; callers get a response rebuilt around the buffered body.
+    async fn send_once(&self, method: &Method, url: &Url, body: Option<&[u8]>, headers: &HeaderMap) -> Result<Response> {
+        if let Some(limiter) = &self.rate_limiter {
+            limiter.acquire().await;
         }
+        let permit = self.inner.governor.acquire().await;
+        let auth_headers = self.inner.auth.authenticate(&AuthRequest { method, url, body }).await?;
         
-        // Join all parameters
-        if !params.is_empty() {
-            url.push_str(&format!("?{}", params.join("&")));
+        let mut request = self.inner.http_client
+            .request(method.clone(), url.clone())
+            .headers(headers.clone())
+            .headers(auth_headers);
+        if let Some(body) = body {
+            request = request
+                .header(CONTENT_TYPE, HeaderValue::from_static("application/json"))
+                .body(body.to_vec());
         }
         
-        let response = self.http_client
-            .get(&url)
-            .headers(self.get_auth_headers()?)
-            .send()
-            .await?;
-        
-        if response.status().is_success() {
-            let transactions: TransactionResponse = response.json().await?;
-            Ok(transactions.records)
-        } else {
-            anyhow::bail!("Failed to fetch sales orders: {}", response.status())
+        let response = request.send().await?;
+        let mut buffered = http::Response::builder().status(response.status()).version(response.version());
+        if let Some(headers) = buffered.headers_mut() {
+            *headers = response.headers().clone();
         }
+        let body = response.bytes().await?;
+        drop(permit);
+        
+        buffered
+            .body(body)
+            .map(Response::from)
+            .map_err(|e| NetSuiteError::InvalidResponse(format!("Failed to buffer response from {}: {}", url, e)))
+    }
+}
+
+/// Lets NetSuite recognize a replayed write and return the original outcome instead of applying it twice
+const IDEMPOTENCY_KEY_HEADER: &str = "X-NetSuite-Idempotency-Key";
+
+/// NetSuite caps SuiteQL pages at 1000 rows
+const SUITEQL_PAGE_SIZE: u32 = 1000;
+
+/// NetSuite caps record collection pages at 1000 records
+const MAX_PAGE_SIZE: u32 = 1000;
+
+/// Records fetched at once while walking a collection when the governor sets no limit
+const UNLIMITED_FETCH_CONCURRENCY: usize = 10;
+
+/// Everything but RFC 3986 unreserved characters is escaped, so spaces become `%20` rather than `+`
+/// and external IDs cannot break out of their path segment
+const URL_ENCODE_SET: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+/// Headers for a write: a fresh idempotency key per logical operation unless the caller supplied one
+fn write_headers(options: &WriteOptions) -> Result<HeaderMap> {
+    let key = options.idempotency_key.clone().unwrap_or_else(|| Uuid::new_v4().to_string());
+    let mut headers = HeaderMap::new();
+    headers.insert(IDEMPOTENCY_KEY_HEADER, HeaderValue::from_str(&key)?);
+    Ok(headers)
+}
+
+/// Append the `replace=` sublists requested in the write options
+fn write_path(path: &str, options: &WriteOptions) -> String {
+    if options.replace.is_empty() {
+        path.to_string()
+    } else {
+        format!("{}?replace={}", path, utf8_percent_encode(&options.replace.join(","), URL_ENCODE_SET))
+    }
+}
+
+fn record_path(record_type: &str, id: &impl std::fmt::Display) -> String {
+    format!("record/v1/{}/{}", record_type, id)
+}
+
+fn external_id_path(record_type: &str, external_id: &str) -> String {
+    format!("record/v1/{}/eid:{}", record_type, utf8_percent_encode(external_id, URL_ENCODE_SET))
+}
+
+fn transform_path(from_type: &str, id: &str, to_type: &str) -> String {
+    format!("record/v1/{}/{}/!transform/{}", from_type, utf8_percent_encode(id, URL_ENCODE_SET), to_type)
+}
+
+/// Pass successful responses through; turn anything else into an error naming the record type and action
+pub(crate) async fn expect_success(response: Response, record_type: &str, action: &str) -> Result<Response> {
+    if response.status().is_success() {
+        Ok(response)
+    } else {
+        Err(NetSuiteError::from_response(response, format!("{} {}", action, record_type)).await)
     }
+}
+
+/// Parse the internal ID of a newly written record from the last segment of its `Location` header
+fn created_from_location<R: Record>(response: &Response) -> Result<Created<R>> {
+    let location = location_of(response, R::RECORD_TYPE)?;
+    let id = last_segment(&location)
+        .parse()
+        .map_err(|_| NetSuiteError::InvalidResponse(format!("Unexpected {} ID in Location header: {}", R::RECORD_TYPE, location)))?;
     
-    fn get_auth_headers(&self) -> Result<HeaderMap> {
-        let mut headers = HeaderMap::new();
-        
-        // For now, we'll use basic auth headers
-        // In production, you'd want to implement proper OAuth 2.0 flow
-        let auth_value = format!("Bearer {}", self.config.consumer_key);
-        headers.insert(AUTHORIZATION, HeaderValue::from_str(&auth_value)?);
-        
-        Ok(headers)
+    Ok(Created { id, location, record: None })
+}
+
+fn location_of(response: &Response, record_type: &str) -> Result<String> {
+    Ok(response
+        .headers()
+        .get(LOCATION)
+        .and_then(|value| value.to_str().ok())
+        .ok_or_else(|| NetSuiteError::InvalidResponse(format!("NetSuite returned no Location header for the new {}", record_type)))?
+        .to_string())
+}
+
+/// Last path segment of a `Location` URL, ignoring a trailing `/`; empty if there is none
+pub(crate) fn last_segment(location: &str) -> &str {
+    location.trim_end_matches('/').rsplit('/').next().unwrap_or_default()
+}
+
+fn vendor_payment_filter(start_date: NaiveDate, end_date: NaiveDate) -> Filter {
+    Filter::field("createddate").between(start_date, end_date)
+}
+
+/// The error `get_item` reports when SuiteQL finds no item, shaped like NetSuite's own 404
+fn item_not_found(id: &str) -> NetSuiteError {
+    NetSuiteError::NotFound(ApiError {
+        status: StatusCode::NOT_FOUND.as_u16(),
+        context: "fetch item".to_string(),
+        title: Some("Record not found".to_string()),
+        details: vec![ErrorDetail {
+            detail: format!("No item with ID {}", id),
+            error_code: Some("NONEXISTENT_ID".to_string()),
+            error_path: None,
+        }],
+        ..ApiError::default()
+    })
+}
+
+/// IDs of the customer's invoices with an unpaid amount, oldest first
+fn open_invoices_query(customer_id: &str) -> Query {
+    Query::select(["id"])
+        .from("transaction")
+        .where_(col("type").eq("CustInvc"))
+        .and(col("entity").eq(customer_id))
+        .and(col("foreignamountunpaid").gt(0))
+        .order_by("trandate", Order::Asc)
+}
+
+/// IDs of vendor payments created on `start_date` through `end_date`. `createddate` is a timestamp,
+/// so the end bound is midnight after `end_date`, matching the REST filter's inclusive `BETWEEN`.
+fn vendor_payments_query(start_date: NaiveDate, end_date: NaiveDate, limit: Option<u32>) -> Query {
+    let day_after_end = end_date.succ_opt().unwrap_or(NaiveDate::MAX);
+    let query = Query::select(["id"])
+        .from("transaction")
+        .where_(col("type").eq("VendPymt"))
+        .and(col("createddate").ge(start_date))
+        .and(col("createddate").lt(day_after_end))
+        .order_by("createddate", Order::Asc);
+    
+    match limit {
+        Some(limit) => query.limit(limit.into()),
+        None => query,
     }
 }
 
+fn vendor_payments_2024_range() -> (NaiveDate, NaiveDate) {
+    (
+        NaiveDate::from_ymd_opt(2024, 5, 1).expect("valid date"),
+        NaiveDate::from_ymd_opt(2024, 8, 31).expect("valid date"),
+    )
+}
+
+#[derive(Debug, Deserialize)]
+struct IdRow {
+    id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ItemTypeRow {
+    itemtype: String,
+    subtype: Option<String>,
+}
+
 // NetSuite data structures
 #[derive(Debug, Deserialize)]
 pub struct CustomerResponse {
@@ -193,13 +916,74 @@ pub struct CustomerResponse {
 }
 
 #[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct Customer {
+    /// Read from responses only; the record URL carries the ID on writes
+    #[serde(default, skip_serializing)]
     pub id: String,
-    pub entityid: String,
-    pub companyname: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external_id: Option<String>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub entity_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub company_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub email: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub phone: Option<String>,
-    pub datecreated: Option<String>,
+    /// Set by NetSuite
+    #[serde(default, skip_serializing)]
+    pub date_created: Option<String>,
+    /// Present when fetched with `expand_sub_resources`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address_book: Option<Sublist<AddressBookEntry>>,
+}
+
+// `addressBook` is a sublist, not a body field: `fields=` cannot select it, so it only comes
+// back through `expandSubResources`.
+impl Record for Customer {
+    const RECORD_TYPE: &'static str = "customer";
+    const FIELDS: &'static [&'static str] = &["externalId", "entityId", "companyName", "email", "phone", "dateCreated"];
+    type Id = String;
+    
+    fn id(&self) -> Option<String> {
+        (!self.id.is_empty()).then(|| self.id.clone())
+    }
+}
+
+/// A line of a customer's `addressBook` sublist
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddressBookEntry {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(default)]
+    pub default_billing: bool,
+    #[serde(default)]
+    pub default_shipping: bool,
+    #[serde(default, rename = "addressBookAddress", skip_serializing_if = "Option::is_none")]
+    pub address: Option<Address>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Address {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub addressee: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub addr1: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub addr2: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub city: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub zip: Option<String>,
+    /// Country code as the `id`, e.g. `US`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub country: Option<EntityReference>,
 }
 
 // New transaction data structures
@@ -209,31 +993,260 @@ pub struct TransactionResponse {
 }
 
 #[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct Transaction {
+    #[serde(default, skip_serializing)]
     pub id: String,
-    pub trandate: Option<String>,
-    pub createddate: Option<String>,
-    pub transaction_type: Option<String>,  // Changed from 'type' to 'transaction_type'
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tran_date: Option<String>,
+    #[serde(default, skip_serializing)]
+    pub created_date: Option<String>,
+    /// e.g. `Check`; set by NetSuite
+    #[serde(default, rename = "type", skip_serializing)]
+    pub transaction_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub memo: Option<String>,
+    /// The record's `total`; set by NetSuite
+    #[serde(default, rename = "total", deserialize_with = "deserialize_optional_number", skip_serializing)]
     pub amount: Option<f64>,
-    pub currency: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub currency: Option<EntityReference>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub entity: Option<EntityReference>,
+    /// Item lines, present when fetched with `expand_sub_resources`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub item: Option<Sublist<TransactionLine>>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+/// A line of a transaction's `item` sublist
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TransactionLine {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line: Option<u32>,
+    pub item: Option<EntityReference>,
+    pub description: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_optional_number")]
+    pub quantity: Option<f64>,
+    #[serde(default, deserialize_with = "deserialize_optional_number")]
+    pub rate: Option<f64>,
+    #[serde(default, deserialize_with = "deserialize_optional_number")]
+    pub amount: Option<f64>,
+}
+
+// Generic transactions are read from the check record, which is what
+// get_transactions_with_filters has always queried; other types use list_as.
+impl Record for Transaction {
+    const RECORD_TYPE: &'static str = "check";
+    const FIELDS: &'static [&'static str] = &["externalId", "tranDate", "createdDate", "type", "memo", "total", "currency", "entity"];
+    type Id = String;
+    
+    fn id(&self) -> Option<String> {
+        (!self.id.is_empty()).then(|| self.id.clone())
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct EntityReference {
     pub id: String,
+    /// Display name; the record API calls it `refName`. Writes only send the ID.
+    #[serde(default, alias = "refName", skip_serializing)]
     pub name: Option<String>,
 }
 
-// Error types - keeping for future use
-#[allow(dead_code)]
-#[derive(Debug, thiserror::Error)]
-pub enum NetSuiteError {
-    #[error("Authentication failed: {0}")]
-    Authentication(String),
-    #[error("API request failed: {0}")]
-    ApiRequest(String),
-    #[error("Invalid response: {0}")]
-    InvalidResponse(String),
+impl EntityReference {
+    /// Reference a record by internal ID, e.g. when setting `entity` or `item` on a write
+    pub fn new(id: impl Into<String>) -> Self {
+        Self { id: id.into(), name: None }
+    }
+}
+
+/// SuiteQL returns numeric columns as strings, the record API as numbers; accept both
+fn deserialize_optional_number<'de, D: serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<Option<f64>, D::Error> {
+    match Option::<serde_json::Value>::deserialize(deserializer)? {
+        Some(serde_json::Value::Number(n)) => Ok(n.as_f64()),
+        Some(serde_json::Value::String(s)) if !s.is_empty() => s.parse().map(Some).map_err(serde::de::Error::custom),
+        _ => Ok(None),
+    }
+}
+
+// Collection page: record lists and SuiteQL results share this shape
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    #[serde(default)]
+    pub has_more: bool,
+    #[serde(default)]
+    pub offset: u64,
+    #[serde(default)]
+    pub count: u64,
+    pub total_results: Option<u64>,
+    #[serde(default)]
+    pub links: Vec<Link>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Link {
+    pub rel: String,
+    pub href: String,
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transform_path_encodes_the_source_id() {
+        assert_eq!(transform_path("salesorder", "123", "invoice"), "record/v1/salesorder/123/!transform/invoice");
+        assert_eq!(transform_path("salesorder", "12/../3?x", "invoice"), "record/v1/salesorder/12%2F..%2F3%3Fx/!transform/invoice");
+    }
+
+    #[test]
+    fn customer_deserializes_from_record_api_payload() {
+        let payload = r#"{
+            "links": [{"rel": "self", "href": "https://123456.suitetalk.api.netsuite.com/services/rest/record/v1/customer/107"}],
+            "id": "107",
+            "externalId": "CRM-0042",
+            "entityId": "CUST-107 Acme Ltd",
+            "companyName": "Acme Ltd",
+            "email": "ap@acme.example",
+            "phone": "555-0100",
+            "dateCreated": "2024-03-05T14:21:00Z",
+            "isPerson": false,
+            "subsidiary": {"id": "1", "refName": "Parent Company"},
+            "addressBook": {
+                "links": [],
+                "items": [{
+                    "links": [],
+                    "id": "3",
+                    "label": "Head office",
+                    "defaultBilling": true,
+                    "defaultShipping": false,
+                    "addressBookAddress": {
+                        "links": [],
+                        "addressee": "Acme Ltd",
+                        "addr1": "1 Main St",
+                        "city": "Springfield",
+                        "state": "IL",
+                        "zip": "62701",
+                        "country": {"id": "US", "refName": "United States"}
+                    }
+                }],
+                "totalResults": 1
+            }
+        }"#;
+        
+        let customer: Customer = serde_json::from_str(payload).expect("customer payload");
+        assert_eq!(customer.id(), Some("107".to_string()));
+        assert_eq!(customer.external_id.as_deref(), Some("CRM-0042"));
+        assert_eq!(customer.entity_id, "CUST-107 Acme Ltd");
+        assert_eq!(customer.company_name.as_deref(), Some("Acme Ltd"));
+        assert_eq!(customer.date_created.as_deref(), Some("2024-03-05T14:21:00Z"));
+        
+        let entries = &customer.address_book.expect("expanded addressBook").items;
+        assert_eq!(entries.len(), 1);
+        assert!(entries[0].default_billing && !entries[0].default_shipping);
+        let address = entries[0].address.as_ref().expect("addressBookAddress");
+        assert_eq!(address.city.as_deref(), Some("Springfield"));
+        assert_eq!(address.country.as_ref().map(|c| c.id.as_str()), Some("US"));
+    }
+    
+    #[test]
+    fn customer_fields_are_body_fields() {
+        assert!(!Customer::FIELDS.contains(&"addressBook"));
+        
+        let fetched: Customer = serde_json::from_str(r#"{"id": "1", "entityId": "Acme", "dateCreated": "2024-03-05T14:21:00Z"}"#).unwrap();
+        assert_eq!(serde_json::to_value(&fetched).unwrap(), serde_json::json!({"entityId": "Acme"}));
+        
+        let changes = Customer {
+            id: String::new(),
+            external_id: None,
+            entity_id: String::new(),
+            company_name: None,
+            email: Some("ap@acme.example".to_string()),
+            phone: None,
+            date_created: None,
+            address_book: None,
+        };
+        assert_eq!(serde_json::to_value(&changes).unwrap(), serde_json::json!({"email": "ap@acme.example"}));
+    }
+    
+    #[test]
+    fn transaction_deserializes_from_record_api_payload() {
+        let payload = r#"{
+            "links": [{"rel": "self", "href": "https://123456.suitetalk.api.netsuite.com/services/rest/record/v1/check/2045"}],
+            "id": "2045",
+            "externalId": "AP-CHK-0091",
+            "tranId": "10091",
+            "tranDate": "2024-08-31",
+            "createdDate": "2024-08-31T16:42:00Z",
+            "type": "Check",
+            "memo": "August rent",
+            "total": 2500.0,
+            "currency": {"links": [], "id": "1", "refName": "USD"},
+            "entity": {"links": [], "id": "77", "refName": "Landlord LLC"},
+            "account": {"links": [], "id": "1", "refName": "1000 Checking"}
+        }"#;
+        
+        let transaction: Transaction = serde_json::from_str(payload).expect("check payload");
+        assert_eq!(transaction.id(), Some("2045".to_string()));
+        assert_eq!(transaction.external_id.as_deref(), Some("AP-CHK-0091"));
+        assert_eq!(transaction.tran_date.as_deref(), Some("2024-08-31"));
+        assert_eq!(transaction.created_date.as_deref(), Some("2024-08-31T16:42:00Z"));
+        assert_eq!(transaction.transaction_type.as_deref(), Some("Check"));
+        assert_eq!(transaction.amount, Some(2500.0));
+        assert_eq!(transaction.currency.as_ref().map(|c| c.id.as_str()), Some("1"));
+        assert_eq!(transaction.entity.as_ref().and_then(|e| e.name.as_deref()), Some("Landlord LLC"));
+        
+        let keys: Vec<String> = serde_json::to_value(&transaction).unwrap().as_object().unwrap().keys().cloned().collect();
+        assert_eq!(keys, ["currency", "entity", "externalId", "memo", "tranDate"]);
+    }
+    
+    #[test]
+    fn vendor_payments_query_covers_the_whole_end_day() {
+        let (start, end) = vendor_payments_2024_range();
+        assert_eq!(
+            vendor_payments_query(start, end, Some(25)).to_sql().unwrap(),
+            "SELECT id FROM transaction WHERE type = 'VendPymt' \
+             AND createddate >= TO_DATE('2024-05-01', 'YYYY-MM-DD') \
+             AND createddate < TO_DATE('2024-09-01', 'YYYY-MM-DD') ORDER BY createddate ASC"
+        );
+        assert_eq!(vendor_payments_query(start, end, Some(25)).row_limit(), Some(25));
+        assert_eq!(vendor_payments_query(start, end, None).row_limit(), None);
+    }
+    
+    #[test]
+    fn open_invoices_query_selects_unpaid_invoices_of_the_customer() {
+        assert_eq!(
+            open_invoices_query("42").to_sql().unwrap(),
+            "SELECT id FROM transaction WHERE type = 'CustInvc' AND entity = '42' \
+             AND foreignamountunpaid > 0 ORDER BY trandate ASC"
+        );
+        assert!(open_invoices_query("42' OR '1' = '1").to_sql().unwrap().contains("entity = '42'' OR ''1'' = ''1'"));
+    }
+    
+    #[test]
+    fn write_headers_use_the_supplied_idempotency_key_or_a_fresh_one() {
+        let supplied = write_headers(&WriteOptions::new().idempotency_key("order-7781")).unwrap();
+        assert_eq!(supplied[IDEMPOTENCY_KEY_HEADER], "order-7781");
+        
+        let first = write_headers(&WriteOptions::default()).unwrap();
+        let second = write_headers(&WriteOptions::default()).unwrap();
+        let first = first[IDEMPOTENCY_KEY_HEADER].to_str().unwrap();
+        assert!(Uuid::parse_str(first).is_ok());
+        assert_ne!(first, second[IDEMPOTENCY_KEY_HEADER].to_str().unwrap());
+    }
+    
+    #[test]
+    fn missing_items_are_not_found_errors() {
+        let NetSuiteError::NotFound(error) = item_not_found("77") else {
+            panic!("expected NotFound");
+        };
+        assert_eq!((error.status, error.context.as_str()), (404, "fetch item"));
+        assert_eq!(error.error_code(), Some("NONEXISTENT_ID"));
+        assert!(error.to_string().contains("No item with ID 77"));
+    }
 }
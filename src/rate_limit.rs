@@ -0,0 +1,135 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+use tokio::time::Instant;
+use tracing::debug;
+
+/// `[netsuite.rate_limit]`: a per-period call budget for the integration role
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RateLimitConfig {
+    /// Sustained rate the budget refills at
+    pub requests_per_minute: u32,
+    /// Requests that may be sent back to back before the rate applies; 1 spaces every request evenly
+    #[serde(default = "default_burst")]
+    pub burst: u32,
+    /// Clients whose configs name the same budget share one bucket within the process
+    #[serde(default)]
+    pub budget: Option<String>,
+}
+
+fn default_burst() -> u32 {
+    1
+}
+
+/// Token bucket: holds up to `burst` tokens, refilled continuously at the configured rate.
+/// Each request takes one token and waits its turn when the bucket is empty.
+#[derive(Debug)]
+pub struct RateLimiter {
+    capacity: f64,
+    tokens_per_sec: f64,
+    bucket: Mutex<Bucket>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    /// Negative when requests have reserved tokens that have not been refilled yet
+    tokens: f64,
+    updated: Instant,
+}
+
+impl RateLimiter {
+    /// Allow `requests_per_minute` on average, with bursts of up to `burst` requests
+    pub fn new(requests_per_minute: u32, burst: u32) -> Self {
+        let capacity = f64::from(burst.max(1));
+        Self {
+            capacity,
+            tokens_per_sec: f64::from(requests_per_minute.max(1)) / 60.0,
+            bucket: Mutex::new(Bucket { tokens: capacity, updated: Instant::now() }),
+        }
+    }
+
+    /// Build the limiter for a config, joining the process-wide shared budget if one is named
+    pub fn from_config(config: &RateLimitConfig) -> Arc<Self> {
+        match &config.budget {
+            Some(name) => Self::shared(name, config.requests_per_minute, config.burst),
+            None => Arc::new(Self::new(config.requests_per_minute, config.burst)),
+        }
+    }
+
+    /// The process-wide limiter registered under `name`, created with these settings on first use.
+    /// Later callers get the existing limiter regardless of the settings they pass.
+    pub fn shared(name: &str, requests_per_minute: u32, burst: u32) -> Arc<Self> {
+        static REGISTRY: OnceLock<Mutex<HashMap<String, Arc<RateLimiter>>>> = OnceLock::new();
+
+        let mut registry = REGISTRY
+            .get_or_init(Default::default)
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        registry
+            .entry(name.to_string())
+            .or_insert_with(|| Arc::new(Self::new(requests_per_minute, burst)))
+            .clone()
+    }
+
+    /// Take a token, waiting until one is available
+    pub async fn acquire(&self) {
+        let wait = self.reserve();
+        if !wait.is_zero() {
+            debug!("Rate limit reached, delaying request by {:?}", wait);
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// Claim the next token and return how long until it is actually available.
+    /// Reserving before sleeping keeps waiting requests in arrival order.
+    fn reserve(&self) -> Duration {
+        let mut bucket = self.bucket.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        let now = Instant::now();
+        let refilled = now.duration_since(bucket.updated).as_secs_f64() * self.tokens_per_sec;
+        bucket.tokens = (bucket.tokens + refilled).min(self.capacity);
+        bucket.updated = now;
+
+        bucket.tokens -= 1.0;
+        if bucket.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-bucket.tokens / self.tokens_per_sec)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test(start_paused = true)]
+    async fn burst_is_free_then_requests_are_spaced_to_the_rate() {
+        let limiter = RateLimiter::new(120, 3);
+
+        for _ in 0..3 {
+            assert_eq!(limiter.reserve(), Duration::ZERO);
+        }
+        assert_eq!(limiter.reserve(), Duration::from_millis(500));
+        assert_eq!(limiter.reserve(), Duration::from_millis(1000));
+
+        tokio::time::advance(Duration::from_secs(10)).await;
+        assert_eq!(limiter.reserve(), Duration::ZERO);
+    }
+
+    #[test]
+    fn shared_budgets_are_one_limiter_per_name() {
+        let first = RateLimiter::shared("rate-limit-test-shared", 60, 2);
+        let second = RateLimiter::shared("rate-limit-test-shared", 600, 50);
+        let other = RateLimiter::shared("rate-limit-test-other", 60, 2);
+
+        assert!(Arc::ptr_eq(&first, &second));
+        assert!(!Arc::ptr_eq(&first, &other));
+        assert_eq!(second.capacity, 2.0);
+
+        let config = RateLimitConfig { requests_per_minute: 60, burst: 2, budget: Some("rate-limit-test-shared".to_string()) };
+        assert!(Arc::ptr_eq(&RateLimiter::from_config(&config), &first));
+    }
+}
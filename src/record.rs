@@ -0,0 +1,222 @@
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::fmt::{Debug, Display};
+use std::str::FromStr;
+
+use crate::filter::Filter;
+
+/// A NetSuite record type that can be fetched and written through the generic CRUD methods
+/// on `NetSuiteClient` (`get`, `list`, `stream`, `create`, `update`, `replace`, `delete`).
+///
+/// Supporting a new record type only takes a serde model and an impl of this trait.
+pub trait Record: Serialize + DeserializeOwned + Send + Sync + 'static {
+    /// Path segment under `record/v1/`, e.g. `customer` or `salesorder`
+    const RECORD_TYPE: &'static str;
+
+    /// Body fields the model deserializes; fetches request only these unless told otherwise.
+    /// Leave empty to fetch whole records.
+    ///
+    /// Models with a flattened `custom_fields` map (and `custom_columns` on their lines) leave
+    /// this empty: that map collects custom fields (`custbody_*`, `custentity_*`, `custitem_*`,
+    /// `custcol_*`) and any other field the struct does not name, which cannot be listed up front.
+    const FIELDS: &'static [&'static str] = &[];
+
+    /// Internal ID type used in record URLs and parsed from `Location` headers
+    type Id: Display + FromStr + Debug + Clone + Send + Sync;
+
+    /// Internal ID of this record, if it has been saved
+    fn id(&self) -> Option<Self::Id>;
+}
+
+/// An entry of a `record/v1/{type}` collection page: NetSuite lists only IDs and links there
+#[derive(Debug, Deserialize)]
+pub struct RecordRef {
+    pub id: String,
+    #[serde(default)]
+    pub links: Vec<crate::netsuite_client::Link>,
+}
+
+/// Outcome of a create: NetSuite answers `204 No Content` and points at the new record
+/// in the `Location` header, from which the internal ID is parsed
+#[derive(Debug)]
+pub struct Created<R: Record> {
+    pub id: R::Id,
+    /// Full URL of the new record
+    pub location: String,
+    /// The stored record, when it was re-fetched after creation
+    pub record: Option<R>,
+}
+
+/// Which body fields a fetch asks NetSuite for (`fields=`)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Fields {
+    /// The model's `Record::FIELDS`, or everything if it declares none
+    #[default]
+    Declared,
+    /// Every field, regardless of what the model declares
+    All,
+    /// Exactly these fields
+    Only(Vec<String>),
+}
+
+/// Field selection and sub-resource expansion for `get_with`, `get_by_external_id_with`,
+/// `stream_with` and `ListQuery::fetch`
+#[derive(Debug, Clone, Default)]
+pub struct FetchOptions {
+    pub fields: Fields,
+    /// Send `expandSubResources=true` so sublists such as `addressBook` or `item` come back inline
+    pub expand_sub_resources: bool,
+}
+
+impl FetchOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn fields<S: Into<String>>(mut self, fields: impl IntoIterator<Item = S>) -> Self {
+        self.fields = Fields::Only(fields.into_iter().map(Into::into).collect());
+        self
+    }
+
+    pub fn all_fields(mut self) -> Self {
+        self.fields = Fields::All;
+        self
+    }
+
+    pub fn expand_sub_resources(mut self) -> Self {
+        self.expand_sub_resources = true;
+        self
+    }
+
+    /// Query parameters for a record type declaring `declared` fields
+    pub(crate) fn query_params(&self, declared: &[&str]) -> Vec<String> {
+        let fields = match &self.fields {
+            Fields::Declared => declared.join(","),
+            Fields::All => String::new(),
+            Fields::Only(fields) => fields.join(","),
+        };
+
+        let mut params = Vec::new();
+        if !fields.is_empty() {
+            params.push(format!("fields={}", fields));
+        }
+        if self.expand_sub_resources {
+            params.push("expandSubResources=true".to_string());
+        }
+        params
+    }
+}
+
+/// An expanded sublist, e.g. a customer's `addressBook` or a transaction's `item` lines.
+/// NetSuite wraps the lines in an object alongside paging links; only the lines are kept.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Sublist<T> {
+    #[serde(default = "Vec::new")]
+    pub items: Vec<T>,
+}
+
+impl<T> Default for Sublist<T> {
+    fn default() -> Self {
+        Self { items: Vec::new() }
+    }
+}
+
+/// Serde adapter for models that keep sublist lines as a plain `Vec<T>`:
+/// `#[serde(rename = "item", default, with = "crate::record::sublist")]`
+pub mod sublist {
+    use super::Sublist;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Serialize)]
+    struct Lines<'a, T> {
+        items: &'a [T],
+    }
+
+    pub fn serialize<T: Serialize, S: Serializer>(items: &[T], serializer: S) -> Result<S::Ok, S::Error> {
+        Lines { items }.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, T: Deserialize<'de>, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<T>, D::Error> {
+        Ok(Sublist::deserialize(deserializer)?.items)
+    }
+}
+
+/// Serde adapter for flattened custom field maps:
+/// `#[serde(flatten, with = "crate::record::custom_fields")]`
+///
+/// Every unmodeled field is read into the map, including read-only ones such as `tranId`
+/// or `shippingAddress_text`. Only custom fields (`cust*`) are written back, so a fetched
+/// record can be modified and sent on an update without NetSuite rejecting those fields.
+pub mod custom_fields {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use serde_json::Value;
+    use std::collections::BTreeMap;
+
+    pub fn serialize<S: Serializer>(fields: &BTreeMap<String, Value>, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(fields.iter().filter(|(name, _)| name.starts_with("cust")))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<BTreeMap<String, Value>, D::Error> {
+        BTreeMap::deserialize(deserializer)
+    }
+}
+
+/// Filter and paging options for `NetSuiteClient::list`
+#[derive(Debug, Clone, Default)]
+pub struct ListQuery {
+    pub filter: Option<Filter>,
+    pub limit: Option<u32>,
+    pub offset: Option<u64>,
+    pub fetch: FetchOptions,
+}
+
+impl ListQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn filter(mut self, filter: Filter) -> Self {
+        self.filter = Some(filter);
+        self
+    }
+
+    pub fn limit(mut self, limit: u32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    pub fn offset(mut self, offset: u64) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    pub fn fetch(mut self, fetch: FetchOptions) -> Self {
+        self.fetch = fetch;
+        self
+    }
+}
+
+/// Options for `create_with`, `update_with`, `replace_with`, `upsert_with` and `delete_with`
+#[derive(Debug, Clone, Default)]
+pub struct WriteOptions {
+    /// Sent as `X-NetSuite-Idempotency-Key`; a random key is generated per operation when unset.
+    /// Supply your own to make a write idempotent across process restarts.
+    pub idempotency_key: Option<String>,
+    /// Sublists whose lines the write replaces (`replace=`) instead of merging into, e.g. `item`
+    pub replace: Vec<String>,
+}
+
+impl WriteOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn idempotency_key(mut self, key: impl Into<String>) -> Self {
+        self.idempotency_key = Some(key.into());
+        self
+    }
+
+    pub fn replace_sublist(mut self, sublist: impl Into<String>) -> Self {
+        self.replace.push(sublist.into());
+        self
+    }
+}
@@ -0,0 +1,184 @@
+use chrono::{DateTime, Utc};
+use rand::Rng;
+use reqwest::{header::{HeaderMap, RETRY_AFTER}, Method, StatusCode};
+use serde::Deserialize;
+use std::time::Duration;
+
+/// When and how long to back off after NetSuite refuses a request.
+///
+/// Requests are retried after 429 (request or concurrency limit, including `CONCURRENCY_LIMIT_EXCEEDED`),
+/// 502/503/504 and transport errors. Delays grow exponentially from `base_delay_ms` up to `max_delay_ms`,
+/// are shortened by a random fraction of up to `jitter`, and never undercut a `Retry-After` header.
+/// By default only idempotent requests are retried: GET, HEAD, PUT, DELETE, SuiteQL queries and
+/// writes carrying an idempotency key.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RetryPolicy {
+    /// Total attempts including the first; 1 disables retries
+    pub max_attempts: u32,
+    pub base_delay_ms: u64,
+    pub max_delay_ms: u64,
+    /// Fraction of each delay (0.0 - 1.0) that is randomized away so clients don't retry in lockstep
+    pub jitter: f64,
+    /// Also retry POST/PATCH requests that carry no idempotency key
+    pub retry_non_idempotent: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 4,
+            base_delay_ms: 500,
+            max_delay_ms: 30_000,
+            jitter: 0.5,
+            retry_non_idempotent: false,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Never retry
+    pub fn none() -> Self {
+        Self { max_attempts: 1, ..Self::default() }
+    }
+
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    pub fn base_delay(mut self, delay: Duration) -> Self {
+        self.base_delay_ms = delay.as_millis() as u64;
+        self
+    }
+
+    pub fn max_delay(mut self, delay: Duration) -> Self {
+        self.max_delay_ms = delay.as_millis() as u64;
+        self
+    }
+
+    pub fn jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    pub fn retry_non_idempotent(mut self, retry: bool) -> Self {
+        self.retry_non_idempotent = retry;
+        self
+    }
+
+    /// Whether a request may be sent again without risking a duplicate side effect
+    pub fn allows(&self, method: &Method, idempotent: bool) -> bool {
+        idempotent
+            || self.retry_non_idempotent
+            || matches!(*method, Method::GET | Method::HEAD | Method::PUT | Method::DELETE | Method::OPTIONS)
+    }
+
+    /// Delay before retry number `retry` (1-based), never shorter than `retry_after`
+    pub fn delay(&self, retry: u32, retry_after: Option<Duration>) -> Duration {
+        let exponential = self.base_delay_ms.saturating_mul(1u64 << retry.saturating_sub(1).min(20));
+        let capped = exponential.min(self.max_delay_ms) as f64;
+        let jitter = self.jitter.clamp(0.0, 1.0);
+        let backoff = Duration::from_millis((capped * (1.0 - jitter * rand::thread_rng().gen::<f64>())) as u64);
+
+        retry_after.map_or(backoff, |retry_after| retry_after.max(backoff))
+    }
+}
+
+/// Statuses worth retrying: throttling and transient gateway/server unavailability
+pub fn is_retryable_status(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::TOO_MANY_REQUESTS | StatusCode::BAD_GATEWAY | StatusCode::SERVICE_UNAVAILABLE | StatusCode::GATEWAY_TIMEOUT
+    )
+}
+
+/// `Retry-After` as a delay. NetSuite sends seconds; the HTTP-date form is accepted too,
+/// and a date in the past means no delay.
+pub fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?;
+    parse_retry_after(value, Utc::now())
+}
+
+fn parse_retry_after(value: &str, now: DateTime<Utc>) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = DateTime::parse_from_rfc2822(value).ok()?;
+    Some((date.with_timezone(&Utc) - now).to_std().unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    fn no_jitter() -> RetryPolicy {
+        RetryPolicy::default().jitter(0.0)
+    }
+
+    #[test]
+    fn retries_throttling_and_gateway_errors_only() {
+        for status in [429, 502, 503, 504] {
+            assert!(is_retryable_status(StatusCode::from_u16(status).unwrap()), "{}", status);
+        }
+        for status in [200, 400, 401, 404, 409, 500] {
+            assert!(!is_retryable_status(StatusCode::from_u16(status).unwrap()), "{}", status);
+        }
+    }
+
+    #[test]
+    fn allows_only_idempotent_requests_by_default() {
+        let policy = RetryPolicy::default();
+        assert!(policy.allows(&Method::GET, false));
+        assert!(policy.allows(&Method::PUT, false));
+        assert!(policy.allows(&Method::DELETE, false));
+        assert!(!policy.allows(&Method::POST, false));
+        assert!(!policy.allows(&Method::PATCH, false));
+        assert!(policy.allows(&Method::POST, true));
+        assert!(policy.clone().retry_non_idempotent(true).allows(&Method::PATCH, false));
+    }
+
+    #[test]
+    fn delay_grows_exponentially_up_to_the_cap() {
+        let policy = no_jitter().base_delay(Duration::from_millis(500)).max_delay(Duration::from_secs(3));
+        assert_eq!(policy.delay(1, None), Duration::from_millis(500));
+        assert_eq!(policy.delay(2, None), Duration::from_millis(1000));
+        assert_eq!(policy.delay(3, None), Duration::from_millis(2000));
+        assert_eq!(policy.delay(4, None), Duration::from_secs(3));
+        assert_eq!(policy.delay(u32::MAX, None), Duration::from_secs(3));
+    }
+
+    #[test]
+    fn jitter_only_shortens_the_delay() {
+        let policy = RetryPolicy::default().jitter(0.5);
+        for _ in 0..100 {
+            let delay = policy.delay(2, None);
+            assert!(delay >= Duration::from_millis(500) && delay <= Duration::from_millis(1000), "{:?}", delay);
+        }
+    }
+
+    #[test]
+    fn delay_never_undercuts_retry_after() {
+        let policy = no_jitter();
+        assert_eq!(policy.delay(1, Some(Duration::from_secs(10))), Duration::from_secs(10));
+        assert_eq!(policy.delay(3, Some(Duration::from_millis(100))), Duration::from_millis(2000));
+    }
+
+    #[test]
+    fn parses_retry_after_seconds_and_dates() {
+        let now = DateTime::parse_from_rfc2822("Wed, 21 Oct 2015 07:27:00 GMT").unwrap().with_timezone(&Utc);
+
+        assert_eq!(parse_retry_after(" 120 ", now), Some(Duration::from_secs(120)));
+        assert_eq!(parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT", now), Some(Duration::from_secs(60)));
+        assert_eq!(parse_retry_after("Wed, 21 Oct 2015 07:00:00 GMT", now), Some(Duration::ZERO));
+        assert_eq!(parse_retry_after("soon", now), None);
+
+        let mut headers = HeaderMap::new();
+        assert_eq!(retry_after(&headers), None);
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("7"));
+        assert_eq!(retry_after(&headers), Some(Duration::from_secs(7)));
+    }
+}
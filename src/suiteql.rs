@@ -0,0 +1,427 @@
+use chrono::NaiveDate;
+use std::fmt;
+
+use crate::error::{NetSuiteError, Result};
+
+/// A SuiteQL literal. Values are always rendered by the builder, never interpolated by hand:
+/// strings are quoted with embedded quotes doubled and dates go through `TO_DATE`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Literal {
+    Text(String),
+    Integer(i64),
+    Number(f64),
+    Bool(bool),
+    Date(NaiveDate),
+}
+
+impl fmt::Display for Literal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Literal::Text(text) => write!(f, "'{}'", text.replace('\'', "''")),
+            Literal::Integer(n) => write!(f, "{}", n),
+            Literal::Number(n) => write!(f, "{}", n),
+            // SuiteQL checkboxes are 'T' / 'F'
+            Literal::Bool(b) => write!(f, "'{}'", if *b { "T" } else { "F" }),
+            Literal::Date(date) => write!(f, "TO_DATE('{}', 'YYYY-MM-DD')", date.format("%Y-%m-%d")),
+        }
+    }
+}
+
+impl From<&str> for Literal {
+    fn from(value: &str) -> Self {
+        Literal::Text(value.to_string())
+    }
+}
+
+impl From<String> for Literal {
+    fn from(value: String) -> Self {
+        Literal::Text(value)
+    }
+}
+
+impl From<i64> for Literal {
+    fn from(value: i64) -> Self {
+        Literal::Integer(value)
+    }
+}
+
+impl From<i32> for Literal {
+    fn from(value: i32) -> Self {
+        Literal::Integer(value.into())
+    }
+}
+
+impl From<u32> for Literal {
+    fn from(value: u32) -> Self {
+        Literal::Integer(value.into())
+    }
+}
+
+impl From<f64> for Literal {
+    fn from(value: f64) -> Self {
+        Literal::Number(value)
+    }
+}
+
+impl From<bool> for Literal {
+    fn from(value: bool) -> Self {
+        Literal::Bool(value)
+    }
+}
+
+impl From<NaiveDate> for Literal {
+    fn from(value: NaiveDate) -> Self {
+        Literal::Date(value)
+    }
+}
+
+/// A column reference, the starting point of a condition
+#[derive(Debug, Clone, PartialEq)]
+pub struct Column(String);
+
+/// Reference a column, e.g. `col("createddate")` or `col("t.type")`
+pub fn col(name: impl Into<String>) -> Column {
+    Column(name.into())
+}
+
+/// A WHERE clause expression
+#[derive(Debug, Clone, PartialEq)]
+pub enum Condition {
+    Compare { column: String, operator: &'static str, value: Literal },
+    Between { column: String, from: Literal, to: Literal },
+    In { column: String, values: Vec<Literal> },
+    IsNull { column: String, negated: bool },
+    And(Vec<Condition>),
+    Or(Vec<Condition>),
+}
+
+impl Column {
+    fn compare(self, operator: &'static str, value: impl Into<Literal>) -> Condition {
+        Condition::Compare { column: self.0, operator, value: value.into() }
+    }
+
+    pub fn eq(self, value: impl Into<Literal>) -> Condition {
+        self.compare("=", value)
+    }
+
+    pub fn ne(self, value: impl Into<Literal>) -> Condition {
+        self.compare("<>", value)
+    }
+
+    pub fn lt(self, value: impl Into<Literal>) -> Condition {
+        self.compare("<", value)
+    }
+
+    pub fn le(self, value: impl Into<Literal>) -> Condition {
+        self.compare("<=", value)
+    }
+
+    pub fn gt(self, value: impl Into<Literal>) -> Condition {
+        self.compare(">", value)
+    }
+
+    pub fn ge(self, value: impl Into<Literal>) -> Condition {
+        self.compare(">=", value)
+    }
+
+    pub fn like(self, pattern: impl Into<String>) -> Condition {
+        self.compare("LIKE", Literal::Text(pattern.into()))
+    }
+
+    pub fn between(self, from: impl Into<Literal>, to: impl Into<Literal>) -> Condition {
+        Condition::Between { column: self.0, from: from.into(), to: to.into() }
+    }
+
+    pub fn in_<V: Into<Literal>>(self, values: impl IntoIterator<Item = V>) -> Condition {
+        Condition::In { column: self.0, values: values.into_iter().map(Into::into).collect() }
+    }
+
+    pub fn is_null(self) -> Condition {
+        Condition::IsNull { column: self.0, negated: false }
+    }
+
+    pub fn is_not_null(self) -> Condition {
+        Condition::IsNull { column: self.0, negated: true }
+    }
+}
+
+impl Condition {
+    pub fn and(self, other: Condition) -> Condition {
+        match self {
+            Condition::And(mut conditions) => {
+                conditions.push(other);
+                Condition::And(conditions)
+            }
+            condition => Condition::And(vec![condition, other]),
+        }
+    }
+
+    pub fn or(self, other: Condition) -> Condition {
+        match self {
+            Condition::Or(mut conditions) => {
+                conditions.push(other);
+                Condition::Or(conditions)
+            }
+            condition => Condition::Or(vec![condition, other]),
+        }
+    }
+
+    fn literals(&self) -> Vec<&Literal> {
+        match self {
+            Condition::Compare { value, .. } => vec![value],
+            Condition::Between { from, to, .. } => vec![from, to],
+            Condition::In { values, .. } => values.iter().collect(),
+            Condition::IsNull { .. } => Vec::new(),
+            Condition::And(conditions) | Condition::Or(conditions) => {
+                conditions.iter().flat_map(Condition::literals).collect()
+            }
+        }
+    }
+
+    fn columns(&self) -> Vec<&str> {
+        match self {
+            Condition::Compare { column, .. }
+            | Condition::Between { column, .. }
+            | Condition::In { column, .. }
+            | Condition::IsNull { column, .. } => vec![column.as_str()],
+            Condition::And(conditions) | Condition::Or(conditions) => {
+                conditions.iter().flat_map(Condition::columns).collect()
+            }
+        }
+    }
+}
+
+impl fmt::Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Condition::Compare { column, operator, value } => write!(f, "{} {} {}", column, operator, value),
+            Condition::Between { column, from, to } => write!(f, "{} BETWEEN {} AND {}", column, from, to),
+            Condition::In { column, values } => {
+                let list = values.iter().map(ToString::to_string).collect::<Vec<_>>();
+                write!(f, "{} IN ({})", column, list.join(", "))
+            }
+            Condition::IsNull { column, negated } => {
+                write!(f, "{} IS {}NULL", column, if *negated { "NOT " } else { "" })
+            }
+            Condition::And(conditions) | Condition::Or(conditions) => {
+                let joiner = if matches!(self, Condition::And(_)) { " AND " } else { " OR " };
+                let parts = conditions
+                    .iter()
+                    .map(|c| match c {
+                        Condition::And(_) | Condition::Or(_) => format!("({})", c),
+                        _ => c.to_string(),
+                    })
+                    .collect::<Vec<_>>();
+                write!(f, "{}", parts.join(joiner))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Order {
+    Asc,
+    Desc,
+}
+
+/// A SELECT statement for `NetSuiteClient::query`.
+///
+/// `Query::select(["id", "tranid"]).from("transaction").where_(col("type").eq("VendPymt")).and(col("createddate").between(d1, d2))`
+/// renders as `SELECT id, tranid FROM transaction WHERE type = 'VendPymt' AND createddate BETWEEN TO_DATE(...) AND TO_DATE(...)`.
+/// Column and table names are checked to be plain identifiers (optionally `name AS alias`) when the SQL is built.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Query {
+    columns: Vec<String>,
+    table: String,
+    condition: Option<Condition>,
+    order_by: Vec<(String, Order)>,
+    limit: Option<u64>,
+}
+
+impl Query {
+    pub fn select<S: Into<String>>(columns: impl IntoIterator<Item = S>) -> Self {
+        Self {
+            columns: columns.into_iter().map(Into::into).collect(),
+            table: String::new(),
+            condition: None,
+            order_by: Vec::new(),
+            limit: None,
+        }
+    }
+
+    pub fn from(mut self, table: impl Into<String>) -> Self {
+        self.table = table.into();
+        self
+    }
+
+    /// Set the WHERE condition, replacing any previous one
+    pub fn where_(mut self, condition: Condition) -> Self {
+        self.condition = Some(condition);
+        self
+    }
+
+    /// AND another condition onto the WHERE clause
+    pub fn and(mut self, condition: Condition) -> Self {
+        self.condition = Some(match self.condition.take() {
+            Some(existing) => existing.and(condition),
+            None => condition,
+        });
+        self
+    }
+
+    /// OR another condition onto the WHERE clause
+    pub fn or(mut self, condition: Condition) -> Self {
+        self.condition = Some(match self.condition.take() {
+            Some(existing) => existing.or(condition),
+            None => condition,
+        });
+        self
+    }
+
+    pub fn order_by(mut self, column: impl Into<String>, order: Order) -> Self {
+        self.order_by.push((column.into(), order));
+        self
+    }
+
+    /// Stop after this many rows; applied by the client while paging
+    pub fn limit(mut self, limit: u64) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    pub fn row_limit(&self) -> Option<u64> {
+        self.limit
+    }
+
+    /// Render the SQL, rejecting anything that is not a plain identifier in name positions
+    /// and numbers SuiteQL cannot represent (NaN, infinities)
+    pub fn to_sql(&self) -> Result<String> {
+        if self.columns.is_empty() {
+            return Err(NetSuiteError::InvalidRequest("SuiteQL query selects no columns".to_string()));
+        }
+        for column in &self.columns {
+            check_column(column)?;
+        }
+        check_identifier(&self.table)?;
+        if let Some(condition) = &self.condition {
+            for column in condition.columns() {
+                check_identifier(column)?;
+            }
+            if let Some(literal) = condition.literals().into_iter().find(|literal| matches!(literal, Literal::Number(n) if !n.is_finite())) {
+                return Err(NetSuiteError::InvalidRequest(format!("SuiteQL query compares against non-finite number {}", literal)));
+            }
+        }
+        for (column, _) in &self.order_by {
+            check_identifier(column)?;
+        }
+
+        let mut sql = format!("SELECT {} FROM {}", self.columns.join(", "), self.table);
+        if let Some(condition) = &self.condition {
+            sql.push_str(&format!(" WHERE {}", condition));
+        }
+        if !self.order_by.is_empty() {
+            let order = self
+                .order_by
+                .iter()
+                .map(|(column, order)| match order {
+                    Order::Asc => format!("{} ASC", column),
+                    Order::Desc => format!("{} DESC", column),
+                })
+                .collect::<Vec<_>>();
+            sql.push_str(&format!(" ORDER BY {}", order.join(", ")));
+        }
+
+        Ok(sql)
+    }
+}
+
+/// `name`, `table.name` or `name AS alias`
+fn check_column(column: &str) -> Result<()> {
+    let parts: Vec<&str> = column.split_whitespace().collect();
+    match parts.as_slice() {
+        ["*"] => Ok(()),
+        [name] => check_identifier(name),
+        [name, keyword, alias] if keyword.eq_ignore_ascii_case("AS") => {
+            check_identifier(name)?;
+            check_identifier(alias)
+        }
+        _ => Err(NetSuiteError::InvalidRequest(format!("Invalid SuiteQL column: {:?}", column))),
+    }
+}
+
+fn check_identifier(name: &str) -> Result<()> {
+    let valid = !name.is_empty()
+        && name.split('.').all(|part| {
+            part.chars().next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+                && part.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        });
+
+    if valid {
+        Ok(())
+    } else {
+        Err(NetSuiteError::InvalidRequest(format!("Invalid SuiteQL identifier: {:?}", name)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn renders_select_with_conditions_and_order() {
+        let query = Query::select(["id", "foreigntotal AS amount"])
+            .from("transaction")
+            .where_(col("type").eq("VendPymt"))
+            .and(col("createddate").between(date(2024, 5, 1), date(2024, 8, 31)))
+            .order_by("createddate", Order::Desc)
+            .limit(10);
+
+        assert_eq!(
+            query.to_sql().unwrap(),
+            "SELECT id, foreigntotal AS amount FROM transaction WHERE type = 'VendPymt' AND createddate BETWEEN \
+             TO_DATE('2024-05-01', 'YYYY-MM-DD') AND TO_DATE('2024-08-31', 'YYYY-MM-DD') ORDER BY createddate DESC"
+        );
+        assert_eq!(query.row_limit(), Some(10));
+    }
+
+    #[test]
+    fn renders_literals() {
+        assert_eq!(Literal::from("O'Brien").to_string(), "'O''Brien'");
+        assert_eq!(Literal::from(r#"a "quoted" \path"#).to_string(), r#"'a "quoted" \path'"#);
+        assert_eq!(Literal::from(42).to_string(), "42");
+        assert_eq!(Literal::from(1.5).to_string(), "1.5");
+        assert_eq!(Literal::from(true).to_string(), "'T'");
+        assert_eq!(Literal::from(date(2024, 1, 9)).to_string(), "TO_DATE('2024-01-09', 'YYYY-MM-DD')");
+    }
+
+    #[test]
+    fn parenthesizes_nested_groups() {
+        let condition = col("t.entity")
+            .in_([1, 2])
+            .and(col("status").eq("A").or(col("status").is_null()));
+        assert_eq!(condition.to_string(), "t.entity IN (1, 2) AND (status = 'A' OR status IS NULL)");
+    }
+
+    #[test]
+    fn rejects_invalid_identifiers() {
+        let base = || Query::select(["id"]).from("transaction");
+
+        assert!(Query::select(["id; DROP"]).from("transaction").to_sql().is_err());
+        assert!(Query::select(["id"]).from("transaction t").to_sql().is_err());
+        assert!(base().where_(col("1type").eq(1)).to_sql().is_err());
+        assert!(base().order_by("createddate DESC", Order::Asc).to_sql().is_err());
+        assert!(Query::select(Vec::<String>::new()).from("transaction").to_sql().is_err());
+        assert!(Query::select(["*"]).from("item").where_(col("t.id").eq(1)).to_sql().is_ok());
+    }
+
+    #[test]
+    fn rejects_non_finite_numbers() {
+        let query = Query::select(["id"]).from("transaction");
+        assert!(query.clone().where_(col("amount").gt(f64::NAN)).to_sql().is_err());
+        assert!(query.where_(col("amount").between(0.0, f64::INFINITY)).to_sql().is_err());
+    }
+}
@@ -0,0 +1,627 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::netsuite_client::{Address, EntityReference, Link};
+use crate::record::Record;
+use crate::transform::TransformInto;
+
+/// A sales order as returned by `record/v1/salesorder`, with its `item` lines.
+///
+/// Build one with `Default` and set what you need to book an order; read-only totals are
+/// never sent back. Lines are only present when fetched with `expand_sub_resources`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SalesOrder {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external_id: Option<String>,
+    /// Document number, e.g. `SO1234`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tran_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tran_date: Option<String>,
+    /// The customer
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entity: Option<EntityReference>,
+    /// e.g. `Pending Fulfillment`; set by NetSuite
+    #[serde(default, skip_serializing)]
+    pub status: Option<EntityReference>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subsidiary: Option<EntityReference>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<EntityReference>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub currency: Option<EntityReference>,
+    /// Customer's PO or web order number
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub other_ref_num: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+    /// Shipping address as a single formatted block
+    #[serde(default, skip_serializing)]
+    pub ship_address: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shipping_address: Option<Address>,
+    #[serde(default, skip_serializing)]
+    pub subtotal: Option<f64>,
+    #[serde(default, skip_serializing)]
+    pub discount_total: Option<f64>,
+    #[serde(default, skip_serializing)]
+    pub tax_total: Option<f64>,
+    #[serde(default, skip_serializing)]
+    pub total: Option<f64>,
+    #[serde(rename = "item", default, with = "crate::record::sublist", skip_serializing_if = "Vec::is_empty")]
+    pub items: Vec<SalesOrderLine>,
+    #[serde(flatten, with = "crate::record::custom_fields")]
+    pub custom_fields: BTreeMap<String, serde_json::Value>,
+    #[serde(default, skip_serializing)]
+    pub links: Vec<Link>,
+}
+
+/// A line of a sales order's `item` sublist
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SalesOrderLine {
+    /// Line number; set it on an update to change an existing line instead of adding one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub item: Option<EntityReference>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quantity: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub amount: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tax_code: Option<EntityReference>,
+    /// Price level; leave unset with an explicit `rate` for custom pricing
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub price: Option<EntityReference>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<EntityReference>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub is_closed: Option<bool>,
+    #[serde(flatten, with = "crate::record::custom_fields")]
+    pub custom_columns: BTreeMap<String, serde_json::Value>,
+    #[serde(default, skip_serializing)]
+    pub links: Vec<Link>,
+}
+
+impl SalesOrderLine {
+    /// A new line for `quantity` of the item with internal ID `item_id`
+    pub fn new(item_id: impl Into<String>, quantity: f64) -> Self {
+        Self {
+            item: Some(EntityReference::new(item_id)),
+            quantity: Some(quantity),
+            ..Self::default()
+        }
+    }
+}
+
+impl Record for SalesOrder {
+    const RECORD_TYPE: &'static str = "salesorder";
+    type Id = String;
+
+    fn id(&self) -> Option<String> {
+        self.id.clone()
+    }
+}
+
+/// A vendor bill as returned by `record/v1/vendorbill`
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VendorBill {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external_id: Option<String>,
+    /// The vendor's invoice number
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tran_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tran_date: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub due_date: Option<String>,
+    /// The vendor
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entity: Option<EntityReference>,
+    /// e.g. `Open` or `Paid In Full`; set by NetSuite
+    #[serde(default, skip_serializing)]
+    pub status: Option<EntityReference>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subsidiary: Option<EntityReference>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub currency: Option<EntityReference>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+    #[serde(default, skip_serializing)]
+    pub total: Option<f64>,
+    #[serde(flatten, with = "crate::record::custom_fields")]
+    pub custom_fields: BTreeMap<String, serde_json::Value>,
+    #[serde(default, skip_serializing)]
+    pub links: Vec<Link>,
+}
+
+impl Record for VendorBill {
+    const RECORD_TYPE: &'static str = "vendorbill";
+    type Id = String;
+
+    fn id(&self) -> Option<String> {
+        self.id.clone()
+    }
+}
+
+/// A bill payment as returned by `record/v1/vendorpayment`. The `apply` sublist lists every open
+/// bill of the vendor; lines with `apply` set are the ones this payment paid.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VendorPayment {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tran_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tran_date: Option<String>,
+    #[serde(default, skip_serializing)]
+    pub created_date: Option<String>,
+    /// The vendor
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entity: Option<EntityReference>,
+    /// Bank account the payment was drawn from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub account: Option<EntityReference>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subsidiary: Option<EntityReference>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub currency: Option<EntityReference>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+    #[serde(default, skip_serializing)]
+    pub status: Option<EntityReference>,
+    #[serde(default, skip_serializing)]
+    pub total: Option<f64>,
+    #[serde(default, with = "crate::record::sublist", skip_serializing_if = "Vec::is_empty")]
+    pub apply: Vec<VendorPaymentApply>,
+    #[serde(flatten, with = "crate::record::custom_fields")]
+    pub custom_fields: BTreeMap<String, serde_json::Value>,
+    #[serde(default, skip_serializing)]
+    pub links: Vec<Link>,
+}
+
+/// A line of a vendor payment's `apply` sublist
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VendorPaymentApply {
+    /// Whether this payment is applied to the document
+    #[serde(default)]
+    pub apply: bool,
+    /// The bill (or other payable) the line refers to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doc: Option<EntityReference>,
+    /// Document number of the bill
+    #[serde(default, skip_serializing)]
+    pub ref_num: Option<String>,
+    /// Document type, e.g. `Bill`
+    #[serde(default, rename = "type", skip_serializing)]
+    pub doc_type: Option<String>,
+    #[serde(default, skip_serializing)]
+    pub apply_date: Option<String>,
+    /// Bill total
+    #[serde(default, skip_serializing)]
+    pub total: Option<f64>,
+    /// Amount that was open on the bill
+    #[serde(default, skip_serializing)]
+    pub due: Option<f64>,
+    /// Amount of this payment applied to the bill
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub amount: Option<f64>,
+    /// Early payment discount taken
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disc: Option<f64>,
+}
+
+/// One bill a vendor payment paid, reconstructed from its `apply` line
+#[derive(Debug, Clone, PartialEq)]
+pub struct BillSettlement {
+    /// Internal ID of the bill
+    pub bill_id: String,
+    /// Document number of the bill
+    pub ref_num: Option<String>,
+    pub amount: f64,
+    pub discount: f64,
+    /// Amount still open after this payment, if NetSuite reported what was due
+    pub remaining: Option<f64>,
+}
+
+impl BillSettlement {
+    /// Whether this payment closed the bill
+    pub fn settled_in_full(&self) -> bool {
+        self.remaining.is_some_and(|remaining| remaining.abs() < 0.005)
+    }
+}
+
+impl VendorPayment {
+    /// The bills this payment paid, in `apply` line order. Requires the payment to have been
+    /// fetched with `expand_sub_resources` (as `get_vendor_payment` does).
+    pub fn settlements(&self) -> Vec<BillSettlement> {
+        self.apply
+            .iter()
+            .filter(|line| line.apply)
+            .filter_map(|line| {
+                let bill_id = line.doc.as_ref()?.id.clone();
+                let amount = line.amount.unwrap_or_default();
+                let discount = line.disc.unwrap_or_default();
+                Some(BillSettlement {
+                    bill_id,
+                    ref_num: line.ref_num.clone(),
+                    amount,
+                    discount,
+                    remaining: line.due.map(|due| due - amount - discount),
+                })
+            })
+            .collect()
+    }
+}
+
+impl Record for VendorPayment {
+    const RECORD_TYPE: &'static str = "vendorpayment";
+    type Id = String;
+
+    fn id(&self) -> Option<String> {
+        self.id.clone()
+    }
+}
+
+/// A line of an invoice's `item` sublist; same layout as a sales order line
+pub type InvoiceLine = SalesOrderLine;
+
+/// A line of a credit memo's `item` sublist; same layout as a sales order line
+pub type CreditMemoLine = SalesOrderLine;
+
+/// An invoice as returned by `record/v1/invoice`, with its `item` lines
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Invoice {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external_id: Option<String>,
+    /// Document number, e.g. `INV1234`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tran_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tran_date: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub due_date: Option<String>,
+    /// The customer
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entity: Option<EntityReference>,
+    /// e.g. `Open` or `Paid In Full`; set by NetSuite
+    #[serde(default, skip_serializing)]
+    pub status: Option<EntityReference>,
+    /// The sales order this invoice was billed from
+    #[serde(default, skip_serializing)]
+    pub created_from: Option<EntityReference>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subsidiary: Option<EntityReference>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub currency: Option<EntityReference>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub terms: Option<EntityReference>,
+    /// Customer's PO number
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub other_ref_num: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+    #[serde(default, skip_serializing)]
+    pub subtotal: Option<f64>,
+    #[serde(default, skip_serializing)]
+    pub tax_total: Option<f64>,
+    #[serde(default, skip_serializing)]
+    pub total: Option<f64>,
+    #[serde(default, skip_serializing)]
+    pub amount_paid: Option<f64>,
+    /// Open balance still owed by the customer
+    #[serde(default, skip_serializing)]
+    pub amount_remaining: Option<f64>,
+    #[serde(rename = "item", default, with = "crate::record::sublist", skip_serializing_if = "Vec::is_empty")]
+    pub items: Vec<InvoiceLine>,
+    #[serde(flatten, with = "crate::record::custom_fields")]
+    pub custom_fields: BTreeMap<String, serde_json::Value>,
+    #[serde(default, skip_serializing)]
+    pub links: Vec<Link>,
+}
+
+impl Record for Invoice {
+    const RECORD_TYPE: &'static str = "invoice";
+    type Id = String;
+
+    fn id(&self) -> Option<String> {
+        self.id.clone()
+    }
+}
+
+/// A credit memo as returned by `record/v1/creditmemo`. Its `apply` sublist lists the
+/// customer's open invoices; lines with `apply` set are the invoices the credit was applied to.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreditMemo {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tran_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tran_date: Option<String>,
+    /// The customer
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entity: Option<EntityReference>,
+    /// e.g. `Open` or `Fully Applied`; set by NetSuite
+    #[serde(default, skip_serializing)]
+    pub status: Option<EntityReference>,
+    /// The invoice or return authorization this credit was created from
+    #[serde(default, skip_serializing)]
+    pub created_from: Option<EntityReference>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subsidiary: Option<EntityReference>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub currency: Option<EntityReference>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+    #[serde(default, skip_serializing)]
+    pub total: Option<f64>,
+    #[serde(default, skip_serializing)]
+    pub applied: Option<f64>,
+    #[serde(default, skip_serializing)]
+    pub unapplied: Option<f64>,
+    #[serde(rename = "item", default, with = "crate::record::sublist", skip_serializing_if = "Vec::is_empty")]
+    pub items: Vec<CreditMemoLine>,
+    #[serde(default, with = "crate::record::sublist", skip_serializing_if = "Vec::is_empty")]
+    pub apply: Vec<CustomerApply>,
+    #[serde(flatten, with = "crate::record::custom_fields")]
+    pub custom_fields: BTreeMap<String, serde_json::Value>,
+    #[serde(default, skip_serializing)]
+    pub links: Vec<Link>,
+}
+
+impl Record for CreditMemo {
+    const RECORD_TYPE: &'static str = "creditmemo";
+    type Id = String;
+
+    fn id(&self) -> Option<String> {
+        self.id.clone()
+    }
+}
+
+/// A customer payment as returned by `record/v1/customerpayment`. Lines of the `apply` sublist
+/// with `apply` set are the invoices the payment was applied to.
+///
+/// Build one with `CustomerPayment::new(customer_id)` and `apply_to(invoice_id, amount)`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomerPayment {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tran_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tran_date: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub customer: Option<EntityReference>,
+    /// Amount received
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payment: Option<f64>,
+    /// Deposit account; leave unset to use Undeposited Funds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub account: Option<EntityReference>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payment_method: Option<EntityReference>,
+    /// Check or reference number
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub check_num: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subsidiary: Option<EntityReference>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub currency: Option<EntityReference>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+    #[serde(default, skip_serializing)]
+    pub status: Option<EntityReference>,
+    #[serde(default, skip_serializing)]
+    pub applied: Option<f64>,
+    #[serde(default, skip_serializing)]
+    pub unapplied: Option<f64>,
+    #[serde(default, with = "crate::record::sublist", skip_serializing_if = "Vec::is_empty")]
+    pub apply: Vec<CustomerApply>,
+    #[serde(flatten, with = "crate::record::custom_fields")]
+    pub custom_fields: BTreeMap<String, serde_json::Value>,
+    #[serde(default, skip_serializing)]
+    pub links: Vec<Link>,
+}
+
+impl CustomerPayment {
+    /// A new payment from the customer with internal ID `customer_id`
+    pub fn new(customer_id: impl Into<String>) -> Self {
+        Self {
+            customer: Some(EntityReference::new(customer_id)),
+            ..Self::default()
+        }
+    }
+
+    /// Apply `amount` of this payment to an invoice, adding it to the payment total
+    pub fn apply_to(mut self, invoice_id: impl Into<String>, amount: f64) -> Self {
+        self.apply.push(CustomerApply::new(invoice_id, amount));
+        self.payment = Some(self.payment.unwrap_or_default() + amount);
+        self
+    }
+
+    /// Invoice IDs and amounts this payment was applied to
+    pub fn applications(&self) -> impl Iterator<Item = (&str, f64)> {
+        self.apply
+            .iter()
+            .filter(|line| line.apply)
+            .filter_map(|line| Some((line.doc.as_ref()?.id.as_str(), line.amount.unwrap_or_default())))
+    }
+}
+
+impl Record for CustomerPayment {
+    const RECORD_TYPE: &'static str = "customerpayment";
+    type Id = String;
+
+    fn id(&self) -> Option<String> {
+        self.id.clone()
+    }
+}
+
+/// A line of a customer payment's or credit memo's `apply` sublist
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomerApply {
+    /// Whether the payment or credit is applied to the document
+    #[serde(default)]
+    pub apply: bool,
+    /// The invoice (or other receivable) the line refers to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doc: Option<EntityReference>,
+    /// Document number of the invoice
+    #[serde(default, skip_serializing)]
+    pub ref_num: Option<String>,
+    /// Document type, e.g. `Invoice`
+    #[serde(default, rename = "type", skip_serializing)]
+    pub doc_type: Option<String>,
+    #[serde(default, skip_serializing)]
+    pub apply_date: Option<String>,
+    /// Invoice total
+    #[serde(default, skip_serializing)]
+    pub total: Option<f64>,
+    /// Amount that was open on the invoice
+    #[serde(default, skip_serializing)]
+    pub due: Option<f64>,
+    /// Amount applied to the invoice
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub amount: Option<f64>,
+    /// Discount taken
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disc: Option<f64>,
+}
+
+impl CustomerApply {
+    /// Apply `amount` to the invoice with internal ID `invoice_id`
+    pub fn new(invoice_id: impl Into<String>, amount: f64) -> Self {
+        Self {
+            apply: true,
+            doc: Some(EntityReference::new(invoice_id)),
+            amount: Some(amount),
+            ..Self::default()
+        }
+    }
+}
+
+impl TransformInto<Invoice> for SalesOrder {}
+impl TransformInto<VendorPayment> for VendorBill {}
+impl TransformInto<CreditMemo> for Invoice {}
+impl TransformInto<CustomerPayment> for Invoice {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn only_custom_fields_are_written_back() {
+        let fetched = json!({
+            "id": "812",
+            "tranId": "SO1234",
+            "status": {"id": "B", "refName": "Pending Fulfillment"},
+            "shippingAddress_text": "Acme Ltd\n1 Main St",
+            "memo": "Rush",
+            "custbody_channel": "web",
+            "item": {"items": [{"line": 1, "quantity": 2.0, "custcol_gift": true, "isClosed": false, "costEstimate": 4.5}]}
+        });
+
+        let mut order: SalesOrder = serde_json::from_value(fetched).unwrap();
+        assert_eq!(order.tran_id.as_deref(), Some("SO1234"));
+        assert_eq!(order.custom_fields["shippingAddress_text"], "Acme Ltd\n1 Main St");
+        assert_eq!(order.items[0].custom_columns["costEstimate"], 4.5);
+
+        order.id = None;
+        order.tran_id = None;
+        let written = serde_json::to_value(&order).unwrap();
+        assert_eq!(written, json!({
+            "memo": "Rush",
+            "custbody_channel": "web",
+            "item": {"items": [{"line": 1, "quantity": 2.0, "isClosed": false, "custcol_gift": true}]}
+        }));
+    }
+
+    #[test]
+    fn settlements_reconstruct_the_bills_a_payment_paid() {
+        let payment: VendorPayment = serde_json::from_value(json!({
+            "id": "5120",
+            "apply": {"items": [
+                {"apply": true, "doc": {"id": "301", "refName": "Bill #INV-77"}, "refNum": "INV-77", "due": 1000.0, "amount": 980.0, "disc": 20.0},
+                {"apply": true, "doc": {"id": "302"}, "refNum": "INV-78", "due": 500.0, "amount": 200.0},
+                {"apply": true, "doc": {"id": "303"}, "amount": 50.0},
+                {"apply": false, "doc": {"id": "304"}, "due": 75.0},
+                {"apply": true, "refNum": "no-doc", "amount": 10.0}
+            ]}
+        }))
+        .unwrap();
+
+        let settlements = payment.settlements();
+        let ids: Vec<&str> = settlements.iter().map(|s| s.bill_id.as_str()).collect();
+        assert_eq!(ids, ["301", "302", "303"]);
+
+        assert_eq!(settlements[0].remaining, Some(0.0));
+        assert!(settlements[0].settled_in_full());
+        assert_eq!(settlements[0].ref_num.as_deref(), Some("INV-77"));
+        assert_eq!((settlements[0].amount, settlements[0].discount), (980.0, 20.0));
+
+        assert_eq!(settlements[1].remaining, Some(300.0));
+        assert!(!settlements[1].settled_in_full());
+
+        assert_eq!(settlements[2].remaining, None);
+        assert!(!settlements[2].settled_in_full());
+    }
+
+    #[test]
+    fn apply_to_adds_lines_and_totals_the_payment() {
+        let payment = CustomerPayment::new("42").apply_to("901", 150.0).apply_to("902", 49.5);
+
+        assert_eq!(payment.payment, Some(199.5));
+        assert_eq!(payment.applications().collect::<Vec<_>>(), [("901", 150.0), ("902", 49.5)]);
+        assert_eq!(
+            serde_json::to_value(&payment).unwrap(),
+            json!({
+                "customer": {"id": "42"},
+                "payment": 199.5,
+                "apply": {"items": [
+                    {"apply": true, "doc": {"id": "901"}, "amount": 150.0},
+                    {"apply": true, "doc": {"id": "902"}, "amount": 49.5}
+                ]}
+            })
+        );
+    }
+
+    #[test]
+    fn applications_skip_unapplied_lines() {
+        let payment: CustomerPayment = serde_json::from_value(json!({
+            "apply": {"items": [
+                {"apply": true, "doc": {"id": "901"}, "amount": 150.0},
+                {"apply": false, "doc": {"id": "903"}, "due": 80.0}
+            ]}
+        }))
+        .unwrap();
+
+        assert_eq!(payment.applications().collect::<Vec<_>>(), [("901", 150.0)]);
+    }
+}
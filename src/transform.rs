@@ -0,0 +1,89 @@
+use crate::error::{NetSuiteError, Result};
+use crate::record::Record;
+
+/// Source record types and the targets NetSuite's REST `!transform` endpoint accepts for each
+pub const TRANSFORMS: &[(&str, &[&str])] = &[
+    ("salesorder", &["invoice", "itemfulfillment", "cashsale"]),
+    ("estimate", &["salesorder", "invoice", "cashsale"]),
+    ("invoice", &["creditmemo", "customerpayment"]),
+    ("purchaseorder", &["vendorbill", "itemreceipt"]),
+    ("vendorbill", &["vendorpayment", "vendorcredit"]),
+    ("returnauthorization", &["creditmemo", "itemreceipt", "cashrefund"]),
+    ("transferorder", &["itemfulfillment", "itemreceipt"]),
+    ("vendorreturnauthorization", &["vendorcredit", "itemfulfillment"]),
+];
+
+/// Marks `Self` as transformable into `To`, so `NetSuiteClient::transform_into` rejects
+/// illegal pairs at compile time. Impls live next to the record models (see `transactions`) and
+/// every one must have a matching row in `TRANSFORMS`, which the tests check.
+pub trait TransformInto<To: Record>: Record {}
+
+/// Outcome of an untyped `NetSuiteClient::transform`
+#[derive(Debug, Clone)]
+pub struct Transformed {
+    /// Record type that was created, e.g. `invoice`
+    pub record_type: String,
+    /// Internal ID of the new record
+    pub id: String,
+    /// Full URL of the new record
+    pub location: String,
+}
+
+/// Targets `from_type` can be transformed into, or `None` if it is not a transform source
+pub fn targets(from_type: &str) -> Option<&'static [&'static str]> {
+    TRANSFORMS
+        .iter()
+        .find(|(source, _)| source.eq_ignore_ascii_case(from_type))
+        .map(|(_, targets)| *targets)
+}
+
+/// Check a pair against `TRANSFORMS`, naming the allowed targets when it is not supported
+pub fn check_transform(from_type: &str, to_type: &str) -> Result<()> {
+    match targets(from_type) {
+        Some(targets) if targets.iter().any(|t| t.eq_ignore_ascii_case(to_type)) => Ok(()),
+        Some(targets) => Err(NetSuiteError::InvalidRequest(format!(
+            "Cannot transform {} into {}; supported targets are: {}",
+            from_type,
+            to_type,
+            targets.join(", ")
+        ))),
+        None => Err(NetSuiteError::InvalidRequest(format!("{} records cannot be transformed", from_type))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transactions::{CreditMemo, CustomerPayment, Invoice, SalesOrder, VendorBill, VendorPayment};
+
+    fn assert_listed<From: TransformInto<To>, To: Record>() {
+        check_transform(From::RECORD_TYPE, To::RECORD_TYPE).unwrap();
+    }
+
+    #[test]
+    fn typed_pairs_are_in_the_table() {
+        assert_listed::<SalesOrder, Invoice>();
+        assert_listed::<Invoice, CreditMemo>();
+        assert_listed::<Invoice, CustomerPayment>();
+        assert_listed::<VendorBill, VendorPayment>();
+    }
+
+    #[test]
+    fn accepts_listed_pairs_case_insensitively() {
+        assert!(check_transform("salesorder", "invoice").is_ok());
+        assert!(check_transform("SalesOrder", "ItemFulfillment").is_ok());
+        assert_eq!(targets("vendorbill"), Some(&["vendorpayment", "vendorcredit"][..]));
+    }
+
+    #[test]
+    fn rejects_pairs_missing_from_the_table() {
+        match check_transform("salesorder", "vendorbill") {
+            Err(NetSuiteError::InvalidRequest(message)) => {
+                assert!(message.contains("invoice, itemfulfillment, cashsale"), "{}", message)
+            }
+            other => panic!("expected InvalidRequest, got {:?}", other),
+        }
+        assert!(matches!(check_transform("customer", "invoice"), Err(NetSuiteError::InvalidRequest(_))));
+        assert!(targets("customer").is_none());
+    }
+}